@@ -1,14 +1,27 @@
 use anyhow::{Result, bail};
+use base64::prelude::*;
 use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
-use kakure_core::BinaryAnalysis;
-use log::{Level, LevelFilter, Record};
+use kakure_core::eh_frame::{CfaRule, RegisterRule};
+use kakure_core::header::ObjectType;
+use kakure_core::{
+    AnalysisOptions, Annotations, Arch, BinaryAnalysis, DemangleStyle, DigestAlgorithm, Format,
+    FunctionNaming, FunctionSignature, KSection, SymbolIndex,
+};
+use log::{Level, LevelFilter};
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::RecursiveMode;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
 use tabled::{Table, Tabled};
 
 /// Available analysis targets
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
 enum AnalysisTarget {
     /// Analyze functions from .eh_frame (unwind info)
     EhFrame,
@@ -16,6 +29,84 @@ enum AnalysisTarget {
     Symtab,
     /// Analyze symbols from .dynsym (dynamic symbol table)
     DynSym,
+    /// Scan executable sections for common function prologues (lossy)
+    TextHeuristic,
+    /// Disassemble executable sections for calls and switch tables, resolving PLT/GOT imports (x86 only)
+    CallGraph,
+}
+
+/// Targets run when --targets is not given
+const DEFAULT_TARGETS: [AnalysisTarget; 2] = [AnalysisTarget::EhFrame, AnalysisTarget::Symtab];
+
+/// The analyses to run, and whether the user named them with --targets
+///
+/// A missing section only fails (exit status 3) a target the user asked for; the
+/// defaults are skipped quietly, as plenty of binaries lack one of them.
+#[derive(Clone, Debug)]
+struct Targets {
+    list: Vec<AnalysisTarget>,
+    explicit: bool,
+}
+
+impl Targets {
+    fn new(requested: Option<Vec<AnalysisTarget>>) -> Self {
+        match requested {
+            Some(list) => Self {
+                list,
+                explicit: true,
+            },
+            None => Self {
+                list: DEFAULT_TARGETS.to_vec(),
+                explicit: false,
+            },
+        }
+    }
+}
+
+/// Log line formats selectable with --log-format
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum LogFormat {
+    /// Colored `[LEVEL] message` lines
+    Human,
+    /// One JSON object per record (timestamp, level, module, message)
+    Json,
+}
+
+/// Container formats selectable with --format
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum FormatArg {
+    /// 32-bit ELF
+    Elf32,
+    /// 64-bit ELF
+    Elf64,
+    /// Mach-O (single architecture)
+    Macho,
+    /// WebAssembly module
+    Wasm,
+    /// Headerless machine code (requires --base and --machine)
+    Raw,
+}
+
+/// Architectures selectable with --machine for raw input
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum MachineArg {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+}
+
+impl MachineArg {
+    /// The ELF `e_machine` value for this architecture
+    fn e_machine(self) -> u16 {
+        match self {
+            MachineArg::X86 => Arch::X86,
+            MachineArg::X86_64 => Arch::X86_64,
+            MachineArg::Arm => Arch::Arm,
+            MachineArg::Aarch64 => Arch::Aarch64,
+        }
+        .machine()
+    }
 }
 
 /// Actions to run after analysis completes
@@ -25,37 +116,279 @@ enum Action {
     ListFunctions,
     /// Dump discovered functions to JSON (--out required)
     DumpJson,
+    /// Dump discovered functions in the format implied by the --out extension
+    /// (.json, .csv, .yaml/.yml, .ndjson); JSON on stdout without --out
+    Dump,
+    /// Emit a SARIF 2.1.0 report flagging oversized functions
+    Sarif,
+    /// Save the analysis as a compact binary report for reloading (--out required)
+    SaveReport,
+    /// Write a Ghidra Python script that creates and names each recovered function
+    ExportGhidra,
+    /// Write an IDAPython script that creates and names each recovered function
+    ExportIda,
     /// No extra action
     None,
 }
 
+/// Inputs, analyses and action shared by `analyze` and `watch`
+#[derive(clap::Args, Debug, Clone)]
+struct AnalyzeArgs {
+    /// Paths to the input binaries
+    #[arg(short, long, num_args = 1.., required_unless_present = "input_list")]
+    input: Vec<String>,
+
+    /// File of further input paths, one per line; blank lines and `#` comments are skipped
+    #[arg(long, value_name = "FILE")]
+    input_list: Option<String>,
+
+    /// Analysis targets to perform
+    #[arg(
+        short,
+        long,
+        value_enum,
+        num_args = 1..,
+        help = "Select one or more analyses to perform [default: eh-frame symtab]"
+    )]
+    targets: Option<Vec<AnalysisTarget>>,
+
+    /// Skip these analyses, even if selected by --targets or by default
+    #[arg(long, value_enum, num_args = 1.., value_name = "TARGET")]
+    exclude_target: Vec<AnalysisTarget>,
+
+    /// Action to run after analyses complete
+    #[arg(long, value_enum, default_value_t = Action::None)]
+    action: Action,
+
+    /// Output path used by some actions (e.g. --action dump-json, --action dump)
+    #[arg(long)]
+    out: Option<String>,
+
+    /// Size (in bytes) above which a function is reported by --action sarif
+    #[arg(long, default_value_t = 4096)]
+    max_function_size: u64,
+
+    /// Embed base64 section bytes in the JSON dump (all sections, or only those named)
+    #[arg(long, num_args = 0.., value_name = "SECTION")]
+    with_data: Option<Vec<String>>,
+
+    /// Embed each function's machine code, base64-encoded, in the dump
+    #[arg(long)]
+    with_bytes: bool,
+
+    /// Drop functions whose boundary confidence (0.0-1.0) is below this
+    #[arg(long, default_value_t = 0.0)]
+    min_confidence: f32,
+
+    /// Keep only exported functions (global, weak or unique symbol binding)
+    #[arg(long, conflicts_with = "only_local")]
+    only_global: bool,
+
+    /// Keep only local (static) functions
+    #[arg(long)]
+    only_local: bool,
+
+    /// Link outlined pieces such as `foo.cold` and `foo.part.0` to their parent `foo`
+    #[arg(long)]
+    link_split_parts: bool,
+
+    /// Print a `summary:` line per input to stderr with function counts by source
+    #[arg(long)]
+    summary: bool,
+
+    /// Trim function ends that run into the next function, optionally only overlaps of at
+    /// most MAX_BYTES (e.g. alignment padding counted in a symbol's size)
+    #[arg(long, value_name = "MAX_BYTES", num_args = 0..=1)]
+    clamp_overlaps: Option<Option<u64>>,
+
+    /// With `list-functions`, print one table per section (`.text`, `.plt`, `.init`, ...)
+    #[arg(long)]
+    group_by_section: bool,
+
+    /// Instead of the action, list what each target finds on its own, one table per
+    /// target, before merging and finalization
+    #[arg(long)]
+    per_source: bool,
+}
+
+impl AnalyzeArgs {
+    /// The `--input` paths followed by those read from `--input-list`
+    fn inputs(&self) -> Result<Vec<String>> {
+        let mut inputs = self.input.clone();
+        if let Some(list) = &self.input_list {
+            inputs.extend(read_input_list(list)?);
+        }
+        if inputs.is_empty() {
+            bail!("No inputs given");
+        }
+        Ok(inputs)
+    }
+}
+
+/// Paths listed in `path`, one per line
+///
+/// Surrounding whitespace is trimmed, and blank lines and lines starting with
+/// `#` are skipped. Relative paths are taken from the current directory.
+fn read_input_list(path: &str) -> Result<Vec<String>> {
+    let list = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read input list '{path}': {e}"))?;
+    Ok(list
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
 /// CLI subcommands
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Perform analysis on a binary and optionally run an action
-    Analyze {
+    Analyze(AnalyzeArgs),
+
+    /// Re-run an analysis and its action whenever an input file changes
+    Watch {
+        #[command(flatten)]
+        analyze: AnalyzeArgs,
+
+        /// Quiet period (in milliseconds) that collapses a burst of writes into one run
+        #[arg(long, default_value_t = 500)]
+        debounce_ms: u64,
+    },
+
+    /// Analyze every file in a directory in parallel, writing one report per input
+    AnalyzeBatch {
+        /// Directory of input binaries (not searched recursively)
+        #[arg(long)]
+        input_dir: String,
+
+        /// Analysis targets to perform [default: eh-frame symtab]
+        #[arg(short, long, value_enum, num_args = 1..)]
+        targets: Option<Vec<AnalysisTarget>>,
+
+        /// Report to write per input (dump-json, sarif or save-report)
+        #[arg(long, value_enum, default_value_t = Action::DumpJson)]
+        action: Action,
+
+        /// Directory receiving one `<input>.json` / `.sarif` / `.kkr` per input
+        #[arg(long)]
+        out_dir: String,
+
+        /// Worker threads (0 uses one per CPU)
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+
+        /// Size (in bytes) above which a function is reported by --action sarif
+        #[arg(long, default_value_t = 4096)]
+        max_function_size: u64,
+    },
+
+    /// Name or comment a function in the input's `<input>.kakure.json` annotations file
+    ///
+    /// Later analyses of the input pick the annotations up automatically.
+    #[command(arg_required_else_help = true)]
+    Annotate {
         /// Path to the input binary
         #[arg(short, long)]
         input: String,
 
-        /// Analysis targets to perform
-        #[arg(
-            short,
-            long,
-            value_enum,
-            num_args = 1..,
-            default_values_t = vec![AnalysisTarget::EhFrame, AnalysisTarget::Symtab],
-            help = "Select one or more analyses to perform"
-        )]
-        targets: Vec<AnalysisTarget>,
-
-        /// Action to run after analyses complete
-        #[arg(long, value_enum, default_value_t = Action::None)]
-        action: Action,
+        /// Start address of the function
+        #[arg(long, value_parser = parse_address)]
+        addr: u64,
+
+        /// Name that replaces whatever the analyzers found
+        #[arg(long, required_unless_present = "comment")]
+        name: Option<String>,
+
+        /// Free-form note kept with the function
+        #[arg(long)]
+        comment: Option<String>,
+    },
 
-        /// Output path used by some actions (e.g. --action dump-json)
+    /// Map addresses (e.g. from a backtrace) to the functions containing them, or else
+    /// to the nearest preceding symbol in the same section
+    Addr2func {
+        /// Path to the input binary
+        #[arg(short, long)]
+        input: String,
+
+        /// Analysis targets to perform [default: eh-frame symtab]
+        #[arg(short, long, value_enum, num_args = 1..)]
+        targets: Option<Vec<AnalysisTarget>>,
+
+        /// Addresses to look up; read one per line from stdin when omitted
+        #[arg(long, num_args = 1.., value_parser = parse_address)]
+        addr: Vec<u64>,
+
+        /// ELF core dump of a process running the input; addresses are taken as
+        /// process addresses and mapped back through the core's file mappings, and
+        /// default to the crashing thread's PC
+        #[arg(long)]
+        core: Option<String>,
+    },
+
+    /// Dump the .eh_frame unwind rules (CFA and saved registers) of the function at an address
+    Cfi {
+        /// Path to the input binary
+        #[arg(short, long)]
+        input: String,
+
+        /// Any address inside the function
+        #[arg(long, value_parser = parse_address)]
+        addr: u64,
+    },
+
+    /// Print aggregate function-size statistics
+    Stats {
+        /// Path to the input binary
+        #[arg(short, long)]
+        input: String,
+
+        /// Analysis targets to perform [default: eh-frame symtab]
+        #[arg(short, long, value_enum, num_args = 1..)]
+        targets: Option<Vec<AnalysisTarget>>,
+
+        /// Emit the statistics as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Output path for --json
         #[arg(long)]
         out: Option<String>,
+
+        /// Estimate the median from a random sample of this many functions instead of
+        /// sorting every size (the other figures stay exact)
+        #[arg(long, value_name = "N", value_parser = parse_sample_size)]
+        sample: Option<usize>,
+    },
+
+    /// Print digests of the whole file or of a single section
+    Digest {
+        /// Path to the input binary
+        #[arg(short, long)]
+        input: String,
+
+        /// Digest only this section instead of the whole file
+        #[arg(short, long)]
+        section: Option<String>,
+
+        /// Algorithms to compute (crc32, md5, sha256); all by default
+        #[arg(short, long, num_args = 1..)]
+        algo: Vec<DigestAlgorithm>,
+    },
+
+    /// Summarize the binary: format, architecture, type, entry point, and what it contains
+    Info {
+        /// Path to the input binary
+        #[arg(short, long)]
+        input: String,
+    },
+
+    /// List the threads and mapped files of an ELF core dump
+    CoreInfo {
+        /// Path to the core dump
+        #[arg(short, long)]
+        input: String,
     },
 
     /// List sections in the binary (like `readelf -S`)
@@ -65,46 +398,396 @@ enum Command {
         input: String,
     },
 
-    /// (Optional) — List symbols (can be implemented later)
-    #[command(hide = true)]
+    /// List symbols (like `readelf -s`), or with --undefined the external references
+    ///
+    /// Defined symbols come from .symtab, or .dynsym when it is stripped, with their
+    /// value, size, type, binding and visibility.
     ListSymbols {
         /// Path to the input binary
         #[arg(short, long)]
         input: String,
+
+        /// List the undefined symbols (external references) of .symtab and .dynsym instead
+        #[arg(long)]
+        undefined: bool,
     },
 }
 
 /// Root CLI
 #[derive(Parser, Debug)]
-#[command(author, version, about = "🧠 Kakure Binary Analysis CLI", long_about = None)]
+#[command(
+    author,
+    version,
+    about = "🧠 Kakure Binary Analysis CLI",
+    long_about = None,
+    after_help = "Exit status: 0 ok, 1 error, 2 no functions found, 3 a requested analysis failed \
+                  or its section is missing, 4 boundary conflicts under --strict"
+)]
 struct Args {
     #[command(subcommand)]
     command: Command,
+
+    /// Disable colored output (the NO_COLOR environment variable is also honored)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Strip decorative emoji from headers
+    #[arg(long, global = true)]
+    no_emoji: bool,
+
+    /// Format of log records on stderr (json for log collectors such as ELK or Loki)
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Human)]
+    log_format: LogFormat,
+
+    /// Parse inputs as this format instead of autodetecting
+    #[arg(long, global = true, value_enum)]
+    format: Option<FormatArg>,
+
+    /// Load address of raw input (used with --format raw)
+    #[arg(long, global = true, value_parser = parse_address, default_value = "0")]
+    base: u64,
+
+    /// Architecture of raw input (used with --format raw)
+    #[arg(long, global = true, value_enum, default_value_t = MachineArg::X86_64)]
+    machine: MachineArg,
+
+    /// Naming for functions without a symbol: `func` (FUNC_0x401000), `ida` (sub_401000),
+    /// or a custom prefix
+    #[arg(long, global = true, value_name = "SCHEME", default_value = "func")]
+    naming: FunctionNaming,
+
+    /// Directories of `.dwo` files, or `.dwp` packages, for `-gsplit-dwarf` builds
+    #[arg(long, global = true, num_args = 1.., value_name = "PATH")]
+    split_dwarf: Vec<PathBuf>,
+
+    /// Merge symbols from the detached debug file under DIR/.build-id/ (e.g.
+    /// /usr/lib/debug) matching the input's build ID
+    #[arg(long, global = true, value_name = "DIR")]
+    debug_root: Option<PathBuf>,
+
+    /// Demangler for displayed names: auto, gnu-v3, rust, msvc or none (raw names stay in dumps)
+    #[arg(long, global = true, value_name = "STYLE", default_value = "auto")]
+    demangle_style: DemangleStyle,
+
+    /// Fail when analyses disagree about where a function ends (build verification)
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Bytes two end addresses may differ by before --strict reports a conflict
+    #[arg(long, global = true, default_value_t = 0)]
+    boundary_tolerance: u64,
+
+    /// Report addresses as if the image were loaded here (e.g. a PIE's base from
+    /// /proc/<pid>/maps) instead of at its linked address
+    #[arg(long, global = true, value_parser = parse_address, conflicts_with = "rebase")]
+    load_base: Option<u64>,
+
+    /// Read at most this many symbols from .symtab and from .dynsym (for huge debug builds)
+    #[arg(long, global = true, value_name = "N")]
+    max_symbols: Option<usize>,
+
+    /// Read unwind info for the eh-frame target from this section instead of `.eh_frame`
+    #[arg(long, global = true, value_name = "SECTION")]
+    eh_frame_section: Option<String>,
+
+    /// Add this slide (e.g. `0x7f0000000000` or `-0x1000`) to every reported address
+    #[arg(long, global = true, value_parser = parse_slide, allow_hyphen_values = true)]
+    rebase: Option<i64>,
+
+    /// Analyze the image starting at this file offset (e.g. one of several concatenated
+    /// firmware images) instead of the whole file
+    #[arg(long, global = true, value_parser = parse_address)]
+    offset: Option<u64>,
+
+    /// Bytes of the image at --offset (default: up to the end of the file)
+    #[arg(long, global = true, value_parser = parse_address, requires = "offset")]
+    length: Option<u64>,
+
+    /// Limit the heuristic and call-graph scans to executable sections; pass `false`
+    /// for code in a section without the executable flag
+    #[arg(
+        long,
+        global = true,
+        value_name = "BOOL",
+        default_value_t = true,
+        action = clap::ArgAction::Set
+    )]
+    only_executable_sections: bool,
+
+    /// Skip naming the entry point, --min-function-size and annotations, leaving the
+    /// functions as the analyzers merged them
+    #[arg(long, global = true)]
+    no_finalize: bool,
+
+    /// Drop functions smaller than this many bytes from the analysis itself, so no
+    /// action or call lookup sees them (functions of unknown size are kept)
+    #[arg(long, global = true, value_name = "BYTES", default_value_t = 0)]
+    min_function_size: u64,
+}
+
+/// Where --load-base or --rebase moves the image
+#[derive(Clone, Copy, Debug)]
+enum Relocation {
+    To(u64),
+    By(i64),
+}
+
+/// Parse a `--sample` size, which must keep at least one function
+fn parse_sample_size(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("the sample needs at least 1 function".to_string()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(format!("invalid sample size '{s}': {e}")),
+    }
+}
+
+/// Parse a decimal or `0x`-prefixed hexadecimal address
+fn parse_address(s: &str) -> Result<u64, String> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    };
+    parsed.map_err(|e| format!("invalid address '{s}': {e}"))
+}
+
+/// Parse a signed decimal or `0x`-prefixed hexadecimal slide
+fn parse_slide(s: &str) -> Result<i64, String> {
+    let (negative, magnitude) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let magnitude = parse_address(magnitude)?;
+    let slide = if negative {
+        0i64.checked_sub_unsigned(magnitude)
+    } else {
+        i64::try_from(magnitude).ok()
+    };
+    slide.ok_or_else(|| format!("slide '{s}' is out of range"))
+}
+
+/// The global options, built once from `Args` and passed to whatever opens an input
+#[derive(Debug, Clone)]
+struct GlobalOptions {
+    /// Format override chosen with --format
+    format: Option<Format>,
+    /// File range chosen with --offset and --length, carved from every input
+    carve: Option<(u64, Option<u64>)>,
+    /// --naming, --demangle-style, --split-dwarf, --max-symbols, --strict,
+    /// --boundary-tolerance, --only-executable-sections and --min-function-size,
+    /// applied to every opened input
+    analysis: AnalysisOptions,
+    /// Root of the build-ID debug file tree given with --debug-root
+    debug_root: Option<PathBuf>,
+    /// Unwind section chosen with --eh-frame-section, read by the eh-frame target
+    eh_frame_section: Option<String>,
+    /// Rebasing chosen with --load-base or --rebase, applied to every analyzed input
+    relocation: Option<Relocation>,
+    /// Cleared by --no-finalize
+    finalize: bool,
+    /// Whether headers are decorated with emoji (cleared by --no-emoji)
+    emoji: bool,
+}
+
+impl GlobalOptions {
+    fn from_args(args: &Args) -> Self {
+        let format = args.format.map(|f| match f {
+            FormatArg::Elf32 => Format::Elf32,
+            FormatArg::Elf64 => Format::Elf64,
+            FormatArg::Macho => Format::MachO,
+            FormatArg::Wasm => Format::Wasm,
+            FormatArg::Raw => Format::Raw {
+                base: args.base,
+                machine: args.machine.e_machine(),
+            },
+        });
+        Self {
+            format,
+            carve: args.offset.map(|offset| (offset, args.length)),
+            analysis: AnalysisOptions {
+                naming: args.naming.clone(),
+                demangle: args.demangle_style,
+                split_dwarf: args.split_dwarf.clone(),
+                max_symbols: args.max_symbols,
+                strict: args.strict,
+                boundary_tolerance: args.boundary_tolerance,
+                only_executable_sections: args.only_executable_sections,
+                min_function_size: args.min_function_size,
+                ..AnalysisOptions::default()
+            },
+            debug_root: args.debug_root.clone(),
+            eh_frame_section: args.eh_frame_section.clone(),
+            relocation: args
+                .load_base
+                .map(Relocation::To)
+                .or(args.rebase.map(Relocation::By)),
+            finalize: !args.no_finalize,
+            emoji: !args.no_emoji,
+        }
+    }
+
+    /// Prefix a header with its decorative glyph unless --no-emoji was given
+    fn heading(&self, glyph: &str, text: &str) -> String {
+        if self.emoji {
+            format!("{glyph} {text}")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Open an input, honoring the --format and --offset options and applying the
+/// analysis options
+fn open_input(opts: &GlobalOptions, input: &str) -> Result<BinaryAnalysis> {
+    let mut analysis = match opts.carve {
+        Some((offset, len)) => BinaryAnalysis::open_at_offset_as(input, offset, len, opts.format)?,
+        None => BinaryAnalysis::open_compressed_as(input, opts.format)?,
+    };
+    analysis.options = opts.analysis.clone();
+    Ok(analysis)
+}
+
+/// Exit status for scripting, raised as problems are found (the highest code wins)
+///
+/// 0 on success, 1 on a hard error, [`EXIT_NO_FUNCTIONS`], [`EXIT_TARGET_FAILED`]
+/// or [`EXIT_BOUNDARY_CONFLICT`] otherwise. Every run (and `watch` iteration)
+/// starts from 0.
+#[derive(Debug, Clone, Copy, Default)]
+struct ExitStatus(u8);
+
+impl ExitStatus {
+    /// Record a problem that should make the process exit with `code`
+    fn raise(&mut self, code: u8) {
+        self.0 = self.0.max(code);
+    }
 }
 
-fn main() -> Result<()> {
-    setup_logger();
+/// An input yielded no functions at all
+const EXIT_NO_FUNCTIONS: u8 = 2;
+/// An analysis failed, or the section of one named with --targets is missing
+const EXIT_TARGET_FAILED: u8 = 3;
+/// --strict found analyses disagreeing about function boundaries
+const EXIT_BOUNDARY_CONFLICT: u8 = 4;
 
-    let args = Args::parse();
+fn main() -> ExitCode {
+    let mut status = ExitStatus::default();
+    match run(Args::parse(), &mut status) {
+        Ok(()) => ExitCode::from(status.0),
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(status.0.max(1))
+        }
+    }
+}
+
+fn run(args: Args, status: &mut ExitStatus) -> Result<()> {
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+    let opts = GlobalOptions::from_args(&args);
+
+    setup_logger(args.log_format);
 
     match args.command {
-        Command::Analyze {
-            input,
+        Command::Analyze(analyze) => run_analysis_and_action(&opts, &analyze, status)?,
+        Command::Watch {
+            analyze,
+            debounce_ms,
+        } => watch(&opts, &analyze, Duration::from_millis(debounce_ms))?,
+        Command::AnalyzeBatch {
+            input_dir,
             targets,
             action,
+            out_dir,
+            threads,
+            max_function_size,
+        } => status.raise(
+            analyze_batch(
+                &opts,
+                &input_dir,
+                &Targets::new(targets),
+                action,
+                &out_dir,
+                threads,
+                max_function_size,
+            )?
+            .0,
+        ),
+        Command::Annotate {
+            input,
+            addr,
+            name,
+            comment,
+        } => annotate(&input, addr, name, comment)?,
+        Command::Addr2func {
+            input,
+            targets,
+            addr,
+            core,
+        } => addr2func(
+            &opts,
+            status,
+            &input,
+            &Targets::new(targets),
+            addr,
+            core.as_deref(),
+        )?,
+        Command::Cfi { input, addr } => print_cfi(&opts, &input, addr)?,
+        Command::Stats {
+            input,
+            targets,
+            json,
             out,
-        } => run_analysis_and_action(&input, targets, action, out)?,
-        Command::ListSections { input } => list_sections(&input)?,
-        Command::ListSymbols { input } => list_symbols(&input)?,
+            sample,
+        } => print_stats(
+            &opts,
+            status,
+            &input,
+            &Targets::new(targets),
+            json,
+            out,
+            sample,
+        )?,
+        Command::Digest {
+            input,
+            section,
+            algo,
+        } => print_digests(&opts, &input, section.as_deref(), &algo)?,
+        Command::Info { input } => print_info(&opts, &input)?,
+        Command::CoreInfo { input } => core_info(&opts, &input)?,
+        Command::ListSections { input } => list_sections(&opts, &input)?,
+        Command::ListSymbols { input, undefined } => {
+            if undefined {
+                list_undefined_symbols(&opts, &input)?
+            } else {
+                list_symbols(&opts, &input)?
+            }
+        }
     }
 
     Ok(())
 }
 
 /// Setup colorful logging
-fn setup_logger() {
-    env_logger::Builder::new()
-        .filter_level(LevelFilter::Info)
+fn setup_logger(format: LogFormat) {
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(LevelFilter::Info);
+
+    if format == LogFormat::Json {
+        builder
+            .format(|buf, record| {
+                let entry = serde_json::json!({
+                    "timestamp": buf.timestamp_millis().to_string(),
+                    "level": record.level().as_str(),
+                    "module": record.module_path(),
+                    "message": strip_ansi(&record.args().to_string()),
+                });
+                writeln!(buf, "{entry}")
+            })
+            .init();
+        return;
+    }
+
+    builder
         .format(|buf, record| {
             let level = match record.level() {
                 Level::Error => "ERROR".red().bold(),
@@ -118,127 +801,1692 @@ fn setup_logger() {
         .init();
 }
 
-/// Run analyses and then perform the chosen action
-fn run_analysis_and_action(
+/// Drop the ANSI color sequences `colored` puts in log messages
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end with a letter, e.g. `\x1b[1;36m`
+            chars.by_ref().find(char::is_ascii_alphabetic);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Open a binary and run the selected analyses over it, finalizing unless --no-finalize
+fn analyze(
+    opts: &GlobalOptions,
+    status: &mut ExitStatus,
     input: &str,
-    targets: Vec<AnalysisTarget>,
-    action: Action,
-    out: Option<String>,
-) -> Result<()> {
+    targets: &Targets,
+) -> Result<BinaryAnalysis> {
+    analyze_with(opts, status, input, targets, opts.finalize)
+}
+
+/// Open a binary and run the selected analyses over it
+///
+/// Without `finalize`, the merged functions are left as the analyzers produced
+/// them: no `entry` naming, --min-function-size or annotations.
+fn analyze_with(
+    opts: &GlobalOptions,
+    status: &mut ExitStatus,
+    input: &str,
+    targets: &Targets,
+    finalize: bool,
+) -> Result<BinaryAnalysis> {
     log::info!("Opening binary: {}", input.bright_blue());
-    let mut analysis = BinaryAnalysis::open(input)?;
+    let mut analysis = open_input(opts, input)?;
 
-    for target in &targets {
-        match target {
+    for target in &targets.list {
+        // The section each target reads; a missing one fails a requested target (exit status 3)
+        let section = match target {
             AnalysisTarget::EhFrame => {
-                log::info!("{}", "Analyzing .eh_frame...".cyan());
-                if let Err(e) = analysis.analyze_eh_frame() {
-                    log::error!("Failed to analyze .eh_frame: {e}");
-                }
+                Some(opts.eh_frame_section.as_deref().unwrap_or(".eh_frame"))
+            }
+            AnalysisTarget::Symtab
+                if !matches!(analysis.header.format_name(), "Mach-O" | "WASM") =>
+            {
+                Some(".symtab")
+            }
+            AnalysisTarget::DynSym => Some(".dynsym"),
+            _ => None,
+        };
+        if let Some(name) = section.filter(|name| analysis.get_section(name).is_none()) {
+            if !targets.explicit {
+                log::info!("No {name} section; skipping it as a default target");
+                continue;
+            }
+            status.raise(EXIT_TARGET_FAILED);
+        }
+
+        let result = match target {
+            AnalysisTarget::EhFrame => {
+                let section = section.unwrap_or(".eh_frame");
+                log::info!("{}", format!("Analyzing {section}...").cyan());
+                let result = if section == ".eh_frame" {
+                    analysis.analyze_eh_frame()
+                } else {
+                    analysis.analyze_eh_frame_in(section)
+                };
+                result.map_err(|e| log::error!("Failed to analyze {section}: {e}"))
+            }
+            AnalysisTarget::Symtab if analysis.header.format_name() == "Mach-O" => {
+                log::info!("{}", "Analyzing LC_SYMTAB...".cyan());
+                analysis
+                    .analyze_macho_symtab()
+                    .map_err(|e| log::error!("Failed to analyze LC_SYMTAB: {e}"))
+            }
+            AnalysisTarget::Symtab if analysis.header.format_name() == "WASM" => {
+                log::info!("{}", "Analyzing WASM name section...".cyan());
+                analysis
+                    .analyze_wasm()
+                    .map_err(|e| log::error!("Failed to analyze WASM module: {e}"))
             }
             AnalysisTarget::Symtab => {
                 log::info!("{}", "Analyzing .symtab...".cyan());
-                if let Err(e) = analysis.analyze_symtab() {
-                    log::error!("Failed to analyze .symtab: {e}");
-                }
+                analysis
+                    .analyze_symtab()
+                    .map_err(|e| log::error!("Failed to analyze .symtab: {e}"))
             }
             AnalysisTarget::DynSym => {
                 log::info!("{}", "Analyzing .dynsym...".cyan());
-                if let Err(e) = analysis.analyze_dynsym() {
-                    log::warn!("DynSym analysis failed or unimplemented: {e}");
-                }
+                analysis
+                    .analyze_dynsym()
+                    .map_err(|e| log::warn!("DynSym analysis failed: {e}"))
+            }
+            AnalysisTarget::TextHeuristic => {
+                log::info!("{}", "Scanning executable sections for prologues...".cyan());
+                analysis
+                    .analyze_text_heuristic()
+                    .map_err(|e| log::error!("Failed to scan for prologues: {e}"))
+            }
+            AnalysisTarget::CallGraph => {
+                log::info!("{}", "Building call graph...".cyan());
+                analysis
+                    .analyze_call_graph()
+                    .map_err(|e| log::error!("Failed to build call graph: {e}"))
             }
+        };
+        if result.is_err() {
+            status.raise(EXIT_TARGET_FAILED);
+        }
+    }
+
+    if let Some(root) = &opts.debug_root {
+        log::info!(
+            "{}",
+            "Loading symbols from the build-ID debug file...".cyan()
+        );
+        if let Err(e) = analysis.load_debug_by_buildid(root) {
+            log::warn!("No debug symbols for {}: {e}", input.bright_blue());
         }
     }
 
-    log::info!("{}", "Finalizing analysis...".green());
-    analysis.identify_entry_point();
-    analysis.sort_functions();
-    analysis.deduplicate_functions();
+    if finalize {
+        log::info!("{}", "Finalizing analysis...".green());
+        analysis.identify_entry_point();
+        analysis.drop_small_functions();
+        analysis.deduplicate_functions();
 
-    match action {
-        Action::None => log::info!("{}", "No post-analysis action requested.".yellow()),
-        Action::ListFunctions => print_function_table(&analysis),
-        Action::DumpJson => dump_functions_json(&analysis, out)?,
+        let sidecar = Annotations::sidecar_path(input);
+        if sidecar.exists() {
+            let annotations = Annotations::load(&sidecar)?;
+            log::info!(
+                "Applying {} annotation(s) from {}",
+                annotations.functions.len(),
+                sidecar.display().to_string().bright_blue()
+            );
+            analysis.apply_annotations(&annotations);
+        }
+    } else {
+        log::info!("{}", "Skipping finalization (--no-finalize)".yellow());
     }
 
-    Ok(())
-}
+    // Annotations are keyed by file addresses, so they go in before rebasing
+    match opts.relocation {
+        Some(Relocation::To(load_base)) => {
+            if analysis.header.object_type() != ObjectType::Dyn {
+                log::warn!(
+                    "{} is not position-independent; rebasing it anyway",
+                    input.bright_blue()
+                );
+            }
+            log::info!("Rebasing image to {:#x}", load_base);
+            analysis.rebase_to(load_base)?;
+        }
+        Some(Relocation::By(slide)) => {
+            log::info!(
+                "Sliding addresses by {}{:#x}",
+                if slide < 0 { "-" } else { "" },
+                slide.unsigned_abs()
+            );
+            analysis.rebase(slide)?;
+        }
+        None if analysis.header.object_type() == ObjectType::Dyn => log::info!(
+            "{}",
+            "Position-independent image: addresses are file-relative and slide by the load base at runtime (see --load-base / --rebase)"
+                .yellow()
+        ),
+        None => {}
+    }
 
-/// Table-friendly view for functions
-#[derive(Tabled)]
-struct FunctionRow {
-    #[tabled(rename = "Function Name")]
-    name: String,
-    #[tabled(rename = "Start Address")]
-    start: String,
-    #[tabled(rename = "End Address")]
-    end: String,
-    #[tabled(rename = "Size (bytes)")]
-    size: String,
+    if let Err(e) = analysis.check_boundaries() {
+        status.raise(EXIT_BOUNDARY_CONFLICT);
+        return Err(e);
+    }
+    if analysis.functions().is_empty() {
+        log::warn!("No functions found in {}", input.bright_blue());
+        status.raise(EXIT_NO_FUNCTIONS);
+    }
+
+    Ok(analysis)
 }
 
-/// Print functions in a formatted table
-fn print_function_table(analysis: &BinaryAnalysis) {
-    let rows: Vec<_> = analysis
-        .functions()
-        .iter()
-        .map(|f| FunctionRow {
-            name: f.function_identifier.clone(),
-            start: format!("0x{:016x}", f.start),
-            end: format!("0x{:016x}", f.end),
-            size: format!("{}", f.size),
-        })
-        .collect();
+/// One machine-readable line on stderr with the analysis summary
+///
+/// e.g. `summary: path=/bin/true format=ELF entry=0x4020 sections=29 functions=42 symtab=30 eh_frame=12`
+fn print_summary(analysis: &BinaryAnalysis) {
+    eprintln!("summary: {}", analysis.summary());
+}
 
-    println!("\n{}", "📘 Discovered Functions".bright_green().bold());
-    let mut table = Table::new(rows);
+/// Record a name and/or comment for the function at `addr` in the input's sidecar file
+fn annotate(input: &str, addr: u64, name: Option<String>, comment: Option<String>) -> Result<()> {
+    if !Path::new(input).is_file() {
+        bail!("'{input}' is not a file");
+    }
+    let sidecar = Annotations::sidecar_path(input);
+    let mut annotations = Annotations::load_or_default(&sidecar)?;
+    annotations.set(addr, name, comment);
+    annotations.save(&sidecar)?;
 
-    let table = table.with(tabled::settings::Style::modern());
-    println!("{table}");
-    println!(
-        "{} {}",
-        "Total functions:".bright_yellow(),
-        analysis.functions().len()
+    log::info!(
+        "{} {:#x} in {}",
+        "Annotated".bright_green(),
+        addr,
+        sidecar.display().to_string().bright_blue()
     );
+    Ok(())
 }
 
-/// Dump functions to JSON
-fn dump_functions_json(analysis: &BinaryAnalysis, out: Option<String>) -> Result<()> {
-    #[derive(serde::Serialize)]
-    struct FuncView<'a> {
-        name: &'a str,
-        start: u64,
-        end: u64,
-        size: u64,
+/// Run analyses and then perform the chosen action
+///
+/// With several inputs, tables are prefixed with each file name, `dump-json` writes
+/// an object keyed by path, and `sarif` writes one run per input.
+fn run_analysis_and_action(
+    opts: &GlobalOptions,
+    args: &AnalyzeArgs,
+    status: &mut ExitStatus,
+) -> Result<()> {
+    let inputs = args.inputs()?;
+    let AnalyzeArgs {
+        input: _,
+        input_list: _,
+        targets,
+        exclude_target,
+        action,
+        out,
+        max_function_size,
+        with_data,
+        with_bytes,
+        min_confidence,
+        only_global,
+        only_local,
+        link_split_parts,
+        summary,
+        clamp_overlaps,
+        group_by_section,
+        per_source,
+    } = args.clone();
+
+    let mut targets = Targets::new(targets);
+    targets
+        .list
+        .retain(|target| !exclude_target.contains(target));
+    if targets.list.is_empty() {
+        bail!("--exclude-target removed every analysis target");
     }
 
-    let view: Vec<_> = analysis
-        .functions()
+    // Checked before analyzing so a bad extension fails fast
+    let dump_format = match (&action, out.as_deref()) {
+        (Action::Dump, Some(path)) => DumpFormat::from_path(path)?,
+        _ => DumpFormat::Json,
+    };
+
+    if per_source {
+        for input in &inputs {
+            print_per_source_tables(opts, status, input, &targets)?;
+        }
+        return Ok(());
+    }
+
+    let mut analyses = inputs
+        .iter()
+        .map(|input| analyze(opts, status, input, &targets))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(max_overlap) = clamp_overlaps {
+        for analysis in &mut analyses {
+            analysis.clamp_overlaps(max_overlap);
+        }
+    }
+    if min_confidence > 0.0 {
+        for analysis in &mut analyses {
+            analysis.retain_min_confidence(min_confidence);
+        }
+    }
+    if only_global || only_local {
+        for analysis in &mut analyses {
+            analysis.retain_binding(only_local);
+        }
+    }
+    if link_split_parts {
+        for analysis in &mut analyses {
+            analysis.link_split_parts();
+        }
+    }
+    if summary {
+        for analysis in &analyses {
+            print_summary(analysis);
+        }
+    }
+
+    match action {
+        Action::None => log::info!("{}", "No post-analysis action requested.".yellow()),
+        Action::ListFunctions => {
+            for analysis in &analyses {
+                if analyses.len() > 1 {
+                    println!(
+                        "\n{}",
+                        opts.heading("📄", &analysis.path).bright_blue().bold()
+                    );
+                }
+                if group_by_section {
+                    print_function_tables_by_section(opts, analysis);
+                } else {
+                    print_function_table(opts, analysis);
+                }
+            }
+        }
+        Action::DumpJson => dump_functions_json(&analyses, out, with_data, with_bytes)?,
+        Action::Dump => dump_functions(&analyses, out, with_data, with_bytes, dump_format)?,
+        Action::Sarif => dump_sarif(&analyses, out, max_function_size)?,
+        Action::SaveReport => {
+            let (Some(out), [analysis]) = (out, analyses.as_slice()) else {
+                bail!("--action save-report takes a single input and --out");
+            };
+            analysis.save_report(&out)?;
+            log::info!(
+                "{} {}",
+                "Report written to:".bright_green(),
+                out.bright_blue()
+            );
+        }
+        Action::ExportGhidra | Action::ExportIda => {
+            let [analysis] = analyses.as_slice() else {
+                bail!("--action export-ghidra / export-ida takes a single input");
+            };
+            write_output(&symbol_script(analysis, &action), out, "Symbol script")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `analyze` once, then again each time one of its inputs changes, until interrupted
+///
+/// The inputs' directories are watched rather than the files, because linkers
+/// usually replace the output instead of rewriting it in place. Changes closer
+/// together than `debounce` trigger a single run, and a failed run (often a
+/// half-written binary) is reported without ending the watch.
+fn watch(opts: &GlobalOptions, args: &AnalyzeArgs, debounce: Duration) -> Result<()> {
+    let watched = args
+        .inputs()?
+        .iter()
+        .map(|input| {
+            let path = Path::new(input);
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            let file_name = path
+                .file_name()
+                .ok_or_else(|| anyhow::anyhow!("'{input}' does not name a file"))?;
+            Ok(dir.canonicalize()?.join(file_name))
+        })
+        .collect::<Result<HashSet<PathBuf>>>()?;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut debouncer = new_debouncer(debounce, tx)?;
+    for dir in watched.iter().filter_map(|path| path.parent()) {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)?;
+    }
+
+    let mut stamps: HashMap<PathBuf, _> = watched
+        .iter()
+        .map(|path| (path.clone(), modification_stamp(path)))
+        .collect();
+    let mut run = 1;
+    run_watched(opts, args, run);
+    for result in rx {
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => {
+                log::error!("Watch error: {e}");
+                continue;
+            }
+        };
+        // Reading the inputs raises access events too; only a changed file counts
+        let changed = events
+            .iter()
+            .filter(|event| watched.contains(&event.path))
+            .any(|event| {
+                let stamp = modification_stamp(&event.path);
+                stamps.insert(event.path.clone(), stamp) != Some(stamp)
+            });
+        if changed {
+            run += 1;
+            run_watched(opts, args, run);
+        }
+    }
+    Ok(())
+}
+
+/// Modification time and length of `path`, or `None` while it does not exist
+fn modification_stamp(path: &Path) -> Option<(std::time::SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// One `watch` iteration, headed by its run number so consecutive outputs stay apart
+fn run_watched(opts: &GlobalOptions, args: &AnalyzeArgs, run: usize) {
+    let rule = "─".repeat(20);
+    println!(
+        "\n{}",
+        format!(
+            "{rule} {} {rule}",
+            opts.heading("🔁", &format!("Run #{run}"))
+        )
+        .bright_blue()
+        .bold()
+    );
+    // Nothing exits between runs, so each run's status is only logged
+    if let Err(e) = run_analysis_and_action(opts, args, &mut ExitStatus::default()) {
+        log::error!("Run #{run} failed: {e:#}");
+    }
+    log::info!("{}", "Waiting for changes (Ctrl-C to stop)...".dimmed());
+}
+
+/// Analyze each file in `input_dir` on a thread pool, writing reports into `out_dir`
+///
+/// A file that fails to open or analyze is logged and skipped; the batch carries on
+/// and the returned status is raised to [`EXIT_TARGET_FAILED`].
+fn analyze_batch(
+    opts: &GlobalOptions,
+    input_dir: &str,
+    targets: &Targets,
+    action: Action,
+    out_dir: &str,
+    threads: usize,
+    max_function_size: u64,
+) -> Result<ExitStatus> {
+    let extension = match action {
+        Action::DumpJson | Action::Dump => "json",
+        Action::Sarif => "sarif",
+        Action::SaveReport => "kkr",
+        Action::ExportGhidra => "ghidra.py",
+        Action::ExportIda => "ida.py",
+        Action::ListFunctions | Action::None => {
+            bail!(
+                "analyze-batch writes files; use --action dump-json, sarif, save-report, export-ghidra or export-ida"
+            )
+        }
+    };
+
+    let mut inputs: Vec<PathBuf> = std::fs::read_dir(input_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    inputs.sort();
+    std::fs::create_dir_all(out_dir)?;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()?;
+    let results: Vec<(bool, ExitStatus)> = pool.install(|| {
+        inputs
+            .par_iter()
+            .map(|path| {
+                let input = path.display().to_string();
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let out = Path::new(out_dir)
+                    .join(format!("{file_name}.{extension}"))
+                    .display()
+                    .to_string();
+
+                let mut file_status = ExitStatus::default();
+                let result =
+                    analyze(opts, &mut file_status, &input, targets).and_then(|analysis| {
+                        match action {
+                            Action::Sarif => dump_sarif(&[analysis], Some(out), max_function_size),
+                            Action::SaveReport => analysis.save_report(out),
+                            Action::ExportGhidra | Action::ExportIda => write_output(
+                                &symbol_script(&analysis, &action),
+                                Some(out),
+                                "Symbol script",
+                            ),
+                            _ => dump_functions_json(&[analysis], Some(out), None, false),
+                        }
+                    });
+                if let Err(e) = &result {
+                    log::error!("Skipping {}: {e}", input.bright_blue());
+                    file_status.raise(EXIT_TARGET_FAILED);
+                }
+                (result.is_err(), file_status)
+            })
+            .collect()
+    });
+
+    let failures = results.iter().filter(|(failed, _)| *failed).count();
+    let mut status = ExitStatus::default();
+    for (_, file_status) in results {
+        status.raise(file_status.0);
+    }
+    let summary = format!(
+        "Batch finished: {} analyzed, {} failed",
+        inputs.len() - failures,
+        failures
+    );
+    if failures == 0 {
+        log::info!("{}", summary.green());
+    } else {
+        log::warn!("{}", summary.yellow());
+    }
+    Ok(status)
+}
+
+/// Print `ADDR NAME+OFFSET` for each address, naming the containing function or else
+/// the nearest symbol below it in the same section, or `ADDR ??` when neither exists
+fn addr2func(
+    opts: &GlobalOptions,
+    status: &mut ExitStatus,
+    input: &str,
+    targets: &Targets,
+    mut addrs: Vec<u64>,
+    core: Option<&str>,
+) -> Result<()> {
+    let analysis = analyze(opts, status, input, targets)?;
+    let core = core.map(BinaryAnalysis::open).transpose()?;
+
+    if let Some(core) = core.as_ref().filter(|_| addrs.is_empty()) {
+        match core.core_threads().first().and_then(|thread| thread.pc) {
+            Some(pc) => addrs.push(pc),
+            None => bail!("The core dump has no thread with a readable PC; pass --addr"),
+        }
+    } else if addrs.is_empty() {
+        for line in std::io::stdin().lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                addrs.push(parse_address(line.trim()).map_err(anyhow::Error::msg)?);
+            }
+        }
+    }
+
+    let mappings = core.as_ref().map(BinaryAnalysis::mapped_files);
+    let input_name = std::path::Path::new(input).file_name();
+    // Addresses outside every function (data, unsized labels) fall back to symbols
+    let symbols = analysis.symbol_index().ok();
+    for addr in addrs {
+        // A process address from the core becomes an address in the input, via the
+        // input's mapping (matched by file name) that covers it
+        let vma = match &mappings {
+            Some(mappings) => {
+                let mapping = mappings.iter().find(|m| m.file_offset_of(addr).is_some());
+                match mapping {
+                    Some(m) if std::path::Path::new(&m.path).file_name() == input_name => m
+                        .file_offset_of(addr)
+                        .and_then(|offset| analysis.vma_at_file_offset(offset)),
+                    Some(m) => {
+                        println!("{:#x} {} (in {})", addr, "??".red(), m.path);
+                        continue;
+                    }
+                    None => None,
+                }
+            }
+            None => Some(addr),
+        };
+
+        match vma
+            .and_then(|vma| analysis.function_at(vma))
+            .and_then(|f| Some((f, f.offset_of(vma?)?)))
+        {
+            Some((f, offset)) => println!(
+                "{:#x} {}+{:#x}",
+                addr,
+                analysis
+                    .demangled_name(f)
+                    .unwrap_or_else(|| f.function_identifier.clone())
+                    .bright_green(),
+                offset
+            ),
+            None => match vma.and_then(|vma| nearest_symbol_in_section(&analysis, &symbols, vma)) {
+                Some((name, offset)) => {
+                    println!("{:#x} {}+{:#x}", addr, name.bright_yellow(), offset)
+                }
+                None => println!("{:#x} {}", addr, "??".red()),
+            },
+        }
+    }
+    Ok(())
+}
+
+/// The closest symbol below `vma` and the offset from it, if both lie in the same section
+///
+/// Past the end of a section the nearest symbol belongs to something unrelated.
+fn nearest_symbol_in_section<'a>(
+    analysis: &BinaryAnalysis,
+    symbols: &Option<SymbolIndex<'a>>,
+    vma: u64,
+) -> Option<(&'a str, u64)> {
+    let (sym, offset) = symbols.as_ref()?.nearest_symbol_below(vma)?;
+    let section = analysis.section_at(vma)?;
+    section
+        .vma_range()
+        .contains(&sym.address)
+        .then_some((sym.name, offset))
+}
+
+/// Table-friendly view for one row of a function's unwind table
+#[derive(Tabled)]
+struct CfiTableRow {
+    #[tabled(rename = "Range")]
+    range: String,
+    #[tabled(rename = "CFA")]
+    cfa: String,
+    #[tabled(rename = "Registers")]
+    registers: String,
+}
+
+/// Print the unwind table covering `addr`, like `readelf --debug-dump=frames-interp`
+fn print_cfi(opts: &GlobalOptions, input: &str, addr: u64) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    let arch = analysis.arch();
+    let register = |n: u16| {
+        arch.dwarf_register_name(n)
+            .map_or_else(|| format!("r{n}"), str::to_string)
+    };
+
+    let rows: Vec<_> = analysis
+        .cfi_rules(addr)?
+        .into_iter()
+        .map(|row| CfiTableRow {
+            range: format!("{:#x}..{:#x}", row.start, row.end),
+            cfa: match row.cfa {
+                CfaRule::RegisterOffset {
+                    register: r,
+                    offset,
+                } => {
+                    format!("{}{:+}", register(r), offset)
+                }
+                CfaRule::Expression => "exp".to_string(),
+            },
+            registers: row
+                .registers
+                .iter()
+                .map(|&(r, rule)| {
+                    let rule = match rule {
+                        RegisterRule::Undefined => "undefined".to_string(),
+                        RegisterRule::SameValue => "same".to_string(),
+                        RegisterRule::Offset(offset) => format!("[cfa{offset:+}]"),
+                        RegisterRule::ValOffset(offset) => format!("cfa{offset:+}"),
+                        RegisterRule::Register(other) => register(other),
+                        RegisterRule::Expression => "[exp]".to_string(),
+                        RegisterRule::ValExpression => "exp".to_string(),
+                        RegisterRule::Architectural => "arch".to_string(),
+                        RegisterRule::Constant(value) => format!("{value:#x}"),
+                    };
+                    format!("{}={}", register(r), rule)
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+        .collect();
+
+    println!(
+        "{} {:#x} in '{}':",
+        opts.heading("🧮", "Unwind rules at").bright_cyan().bold(),
+        addr,
+        input.bright_blue()
+    );
+    let mut table = Table::new(rows);
+    let table = table.with(tabled::settings::Style::modern());
+    println!("{table}");
+    Ok(())
+}
+
+/// Table-friendly view for functions
+#[derive(Tabled)]
+struct FunctionRow {
+    #[tabled(rename = "Function Name")]
+    name: String,
+    #[tabled(rename = "Start Address")]
+    start: String,
+    #[tabled(rename = "End Address")]
+    end: String,
+    #[tabled(rename = "Size (bytes)")]
+    size: String,
+    #[tabled(rename = "Source")]
+    source: String,
+    #[tabled(rename = "Confidence")]
+    confidence: String,
+}
+
+/// Print functions in a formatted table
+fn print_function_table(opts: &GlobalOptions, analysis: &BinaryAnalysis) {
+    let rows: Vec<_> = analysis
+        .functions()
+        .iter()
+        .map(|f| function_row(analysis, f))
+        .collect();
+
+    println!(
+        "\n{}",
+        opts.heading("📘", "Discovered Functions")
+            .bright_green()
+            .bold()
+    );
+    let mut table = Table::new(rows);
+
+    let table = table.with(tabled::settings::Style::modern());
+    println!("{table}");
+    println!(
+        "{} {}",
+        "Total functions:".bright_yellow(),
+        analysis.functions().len()
+    );
+    print_file_relative_hint(analysis);
+}
+
+/// Print one function table per target, each run alone on a fresh copy of `input`
+///
+/// Shows what every analyzer contributes before sources are merged by priority
+/// and before finalization renames or drops anything.
+fn print_per_source_tables(
+    opts: &GlobalOptions,
+    status: &mut ExitStatus,
+    input: &str,
+    targets: &Targets,
+) -> Result<()> {
+    println!("\n{}", opts.heading("📄", input).bright_blue().bold());
+    for target in &targets.list {
+        let single = Targets {
+            list: vec![target.clone()],
+            ..targets.clone()
+        };
+        let analysis = analyze_with(opts, status, input, &single, false)?;
+        let rows: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| function_row(&analysis, f))
+            .collect();
+        let name = target
+            .to_possible_value()
+            .map_or_else(|| format!("{target:?}"), |v| v.get_name().to_string());
+        println!(
+            "\n{}",
+            opts.heading("📘", &format!("{name} ({} functions)", rows.len()))
+                .bright_green()
+                .bold()
+        );
+        let mut table = Table::new(rows);
+        println!("{}", table.with(tabled::settings::Style::modern()));
+    }
+    Ok(())
+}
+
+/// Print one function table per owning section, in address order
+///
+/// Functions outside every allocated section are gathered last under "(no section)".
+fn print_function_tables_by_section(opts: &GlobalOptions, analysis: &BinaryAnalysis) {
+    let mut groups: Vec<(Option<&KSection>, Vec<FunctionRow>)> = Vec::new();
+    for f in analysis.functions() {
+        let section = analysis.section_at(f.start);
+        let row = function_row(analysis, f);
+        match groups
+            .iter_mut()
+            .find(|(s, _)| s.map(|s| &s.name) == section.map(|s| &s.name))
+        {
+            Some((_, rows)) => rows.push(row),
+            None => groups.push((section, vec![row])),
+        }
+    }
+    groups.sort_by_key(|(section, _)| section.map_or(u64::MAX, |s| s.vma));
+
+    for (section, rows) in groups {
+        let name = section.map_or("(no section)", |s| s.name.as_str());
+        let count = rows.len();
+        println!(
+            "\n{}",
+            opts.heading("📘", &format!("{name} ({count} functions)"))
+                .bright_green()
+                .bold()
+        );
+        let mut table = Table::new(rows);
+        let table = table.with(tabled::settings::Style::modern());
+        println!("{table}");
+    }
+    println!(
+        "{} {}",
+        "Total functions:".bright_yellow(),
+        analysis.functions().len()
+    );
+    print_file_relative_hint(analysis);
+}
+
+/// Table row for one function, showing a split part's parent beside its name
+fn function_row(analysis: &BinaryAnalysis, f: &FunctionSignature) -> FunctionRow {
+    FunctionRow {
+        name: {
+            let name = analysis
+                .demangled_name(f)
+                .unwrap_or_else(|| f.function_identifier.clone());
+            match &f.parent {
+                Some(parent) => format!("{name} (part of {parent})"),
+                None => name,
+            }
+        },
+        start: format!("0x{:016x}", f.start),
+        end: format!("0x{:016x}", f.end),
+        size: format!("{}", f.size),
+        source: analysis
+            .function_source(f.start)
+            .map_or_else(|| "-".to_string(), |s| s.to_string()),
+        confidence: format!("{:.2}", f.confidence),
+    }
+}
+
+/// Remind that a PIE or shared object's addresses don't include its load base
+fn print_file_relative_hint(analysis: &BinaryAnalysis) {
+    if analysis.header.object_type() == ObjectType::Dyn && analysis.load_bias() == 0 {
+        println!(
+            "{}",
+            "Addresses are file-relative; add the runtime load base or pass --load-base".yellow()
+        );
+    }
+}
+
+/// Output formats for `--action dump`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DumpFormat {
+    Json,
+    Csv,
+    Yaml,
+    Ndjson,
+}
+
+impl DumpFormat {
+    /// Infer the format from the extension of `path`
+    fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => Ok(DumpFormat::Json),
+            Some("csv") => Ok(DumpFormat::Csv),
+            Some("yaml" | "yml") => Ok(DumpFormat::Yaml),
+            Some("ndjson" | "jsonl") => Ok(DumpFormat::Ndjson),
+            _ => bail!(
+                "Cannot infer a dump format from '{path}'; use a .json, .csv, .yaml or .ndjson extension"
+            ),
+        }
+    }
+}
+
+/// Dump functions in `format`
+///
+/// JSON and YAML share the `dump-json` layout. CSV and NDJSON write one row per
+/// function with a leading `path` column, so several inputs can share a file; they
+/// have no room for section bytes and reject `--with-data`.
+fn dump_functions(
+    analyses: &[BinaryAnalysis],
+    out: Option<String>,
+    with_data: Option<Vec<String>>,
+    with_bytes: bool,
+    format: DumpFormat,
+) -> Result<()> {
+    if with_data.is_some() && matches!(format, DumpFormat::Csv | DumpFormat::Ndjson) {
+        bail!("--with-data needs a JSON or YAML dump");
+    }
+
+    match format {
+        DumpFormat::Json => dump_functions_json(analyses, out, with_data, with_bytes),
+        DumpFormat::Yaml => {
+            let yaml = serde_yaml::to_string(&functions_value(analyses, &with_data, with_bytes)?)?;
+            write_output(yaml.trim_end(), out, "YAML dump")
+        }
+        DumpFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record([
+                "path",
+                "name",
+                "demangled",
+                "start",
+                "end",
+                "size",
+                "visibility",
+                "binding",
+                "source",
+                "is_ifunc",
+                "has_exception_handling",
+                "confidence",
+                "calling_convention",
+                "calls",
+                "comment",
+                "parent",
+                "bytes",
+            ])?;
+            for analysis in analyses {
+                for f in function_views(analysis, with_bytes) {
+                    writer.write_record([
+                        analysis.path.clone(),
+                        f.name.to_string(),
+                        f.demangled.unwrap_or_default(),
+                        format!("{:#x}", f.start),
+                        format!("{:#x}", f.end),
+                        f.size.to_string(),
+                        f.visibility.unwrap_or_default(),
+                        f.binding.unwrap_or_default(),
+                        f.source.unwrap_or_default(),
+                        f.is_ifunc.to_string(),
+                        f.has_exception_handling.to_string(),
+                        format!("{:.2}", f.confidence),
+                        f.calling_convention.unwrap_or_default(),
+                        f.calls.join(";"),
+                        f.comment.unwrap_or_default().to_string(),
+                        f.parent.unwrap_or_default().to_string(),
+                        f.bytes.unwrap_or_default(),
+                    ])?;
+                }
+            }
+            let csv = String::from_utf8(writer.into_inner()?)?;
+            write_output(csv.trim_end(), out, "CSV dump")
+        }
+        DumpFormat::Ndjson => {
+            let mut lines = Vec::new();
+            for analysis in analyses {
+                for f in function_views(analysis, with_bytes) {
+                    let mut row = serde_json::Map::new();
+                    row.insert("path".to_string(), analysis.path.clone().into());
+                    if let serde_json::Value::Object(fields) = serde_json::to_value(&f)? {
+                        row.extend(fields);
+                    }
+                    lines.push(serde_json::to_string(&row)?);
+                }
+            }
+            write_output(&lines.join("\n"), out, "NDJSON dump")
+        }
+    }
+}
+
+/// Dump functions to JSON
+///
+/// A single analysis is written as-is; several are written as an object keyed by path.
+fn dump_functions_json(
+    analyses: &[BinaryAnalysis],
+    out: Option<String>,
+    with_data: Option<Vec<String>>,
+    with_bytes: bool,
+) -> Result<()> {
+    let json = functions_value(analyses, &with_data, with_bytes)?;
+    write_output(&serde_json::to_string_pretty(&json)?, out, "JSON dump")
+}
+
+/// The `dump-json` document: one analysis as-is, several keyed by path
+fn functions_value(
+    analyses: &[BinaryAnalysis],
+    with_data: &Option<Vec<String>>,
+    with_bytes: bool,
+) -> Result<serde_json::Value> {
+    Ok(match analyses {
+        [analysis] => functions_json(analysis, with_data, with_bytes)?,
+        _ => serde_json::Value::Object(
+            analyses
+                .iter()
+                .map(|a| Ok((a.path.clone(), functions_json(a, with_data, with_bytes)?)))
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
+/// One function as written by the dump actions
+#[derive(serde::Serialize)]
+struct FuncView<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    demangled: Option<String>,
+    start: u64,
+    end: u64,
+    size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_ifunc: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    has_exception_handling: bool,
+    /// Rounded to 2 decimals, as the table shows it, so `0.8` doesn't come out as
+    /// `0.800000011920929`
+    confidence: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    calling_convention: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    calls: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<String>,
+}
+
+/// Dump view of every function in `analysis`, with its code when `with_bytes`
+fn function_views(analysis: &BinaryAnalysis, with_bytes: bool) -> Vec<FuncView<'_>> {
+    analysis
+        .functions()
         .iter()
         .map(|f| FuncView {
             name: &f.function_identifier,
+            demangled: analysis.demangled_name(f),
             start: f.start,
             end: f.end,
             size: f.size,
+            visibility: f.visibility.map(|v| v.to_string()),
+            binding: f.binding.map(|b| b.to_string()),
+            source: analysis.function_source(f.start).map(|s| s.to_string()),
+            is_ifunc: f.is_ifunc,
+            has_exception_handling: f.has_exception_handling,
+            confidence: (f64::from(f.confidence) * 100.0).round() / 100.0,
+            calling_convention: analysis.calling_convention(f.start).map(|c| c.to_string()),
+            calls: analysis.calls_from(f),
+            comment: analysis.comment(f.start),
+            parent: f.parent.as_deref(),
+            bytes: with_bytes
+                .then(|| f.bytes(analysis))
+                .flatten()
+                .map(|code| BASE64_STANDARD.encode(code)),
+        })
+        .collect()
+}
+
+/// JSON view of one analysis's functions
+///
+/// With `with_data`, the dump becomes a self-contained snapshot object holding the
+/// functions plus a `sections` array with base64-encoded section bytes.
+fn functions_json(
+    analysis: &BinaryAnalysis,
+    with_data: &Option<Vec<String>>,
+    with_bytes: bool,
+) -> Result<serde_json::Value> {
+    #[derive(serde::Serialize)]
+    struct SectionView<'a> {
+        name: &'a str,
+        vma: u64,
+        size: u64,
+        file_offset: u64,
+        flags: u64,
+        data: String,
+    }
+
+    /// What `from_json_dump` needs to rebuild the header
+    #[derive(serde::Serialize)]
+    struct HeaderView {
+        format: &'static str,
+        machine: u16,
+        is_64: bool,
+        little_endian: bool,
+        entry: u64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Snapshot<'a> {
+        path: &'a str,
+        header: HeaderView,
+        functions: Vec<FuncView<'a>>,
+        sections: Vec<SectionView<'a>>,
+    }
+
+    let view = function_views(analysis, with_bytes);
+
+    let json = match with_data {
+        None => serde_json::to_value(&view)?,
+        Some(names) => {
+            let sections = analysis
+                .sections()
+                .iter()
+                .filter(|sh| names.is_empty() || names.contains(&sh.name))
+                .map(|sh| SectionView {
+                    name: &sh.name,
+                    vma: sh.vma,
+                    size: sh.size,
+                    file_offset: sh.file_offset,
+                    flags: sh.flags,
+                    data: BASE64_STANDARD.encode(sh.raw_data()),
+                })
+                .collect();
+            let header = &analysis.header;
+            serde_json::to_value(&Snapshot {
+                path: &analysis.path,
+                header: HeaderView {
+                    format: header.format_name(),
+                    machine: header.machine(),
+                    is_64: header.is_64(),
+                    little_endian: header.is_little_endian(),
+                    entry: header.entry_point(),
+                },
+                functions: view,
+                sections,
+            })?
+        }
+    };
+
+    Ok(json)
+}
+
+/// Emit a SARIF 2.1.0 log with one run per analysis
+fn dump_sarif(analyses: &[BinaryAnalysis], out: Option<String>, max_size: u64) -> Result<()> {
+    let runs: Vec<_> = analyses
+        .iter()
+        .map(|analysis| sarif_run(analysis, max_size))
+        .collect();
+
+    let sarif = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": runs
+    });
+
+    let json = serde_json::to_string_pretty(&sarif)?;
+    write_output(&json, out, "SARIF report")
+}
+
+/// A SARIF run with one `function-size` result per oversized function
+fn sarif_run(analysis: &BinaryAnalysis, max_size: u64) -> serde_json::Value {
+    let results: Vec<_> = analysis
+        .functions()
+        .iter()
+        .filter(|f| f.size > max_size)
+        .map(|f| {
+            serde_json::json!({
+                "ruleId": "function-size",
+                "level": "warning",
+                "message": {
+                    "text": format!(
+                        "Function '{}' at {:#x} is {} bytes (threshold {})",
+                        f.function_identifier, f.start, f.size, max_size
+                    )
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": analysis.path },
+                        "address": {
+                            "absoluteAddress": f.start,
+                            "length": f.size,
+                            "name": f.function_identifier,
+                            "kind": "function"
+                        }
+                    },
+                    "logicalLocations": [{
+                        "name": f.function_identifier,
+                        "kind": "function"
+                    }]
+                }]
+            })
         })
         .collect();
 
-    let json = serde_json::to_string_pretty(&view)?;
+    serde_json::json!({
+        "tool": {
+            "driver": {
+                "name": "kakure",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": [{
+                    "id": "function-size",
+                    "name": "FunctionSize",
+                    "shortDescription": {
+                        "text": "Function exceeds the configured size threshold"
+                    },
+                    "defaultConfiguration": { "level": "warning" }
+                }]
+            }
+        },
+        "artifacts": [{ "location": { "uri": analysis.path } }],
+        "results": results
+    })
+}
+
+/// Recreates the functions in Ghidra: run from the Script Manager with the binary open
+const GHIDRA_LOADER: &str = r#"from ghidra.program.model.symbol import SourceType
+
+delta = currentProgram.getImageBase().getOffset() - IMAGE_BASE
+for start, size, name in FUNCTIONS:
+    addr = toAddr(start + delta)
+    func = getFunctionAt(addr)
+    if func is None:
+        func = createFunction(addr, name)
+        if func is None:
+            print("kakure: could not create a function at %s" % addr)
+    elif name is not None:
+        func.setName(name, SourceType.IMPORTED)
+"#;
+
+/// Recreates the functions in IDA: run with File > Script file
+const IDA_LOADER: &str = r#"import ida_funcs
+import ida_idaapi
+import ida_nalt
+import ida_name
+
+delta = ida_nalt.get_imagebase() - IMAGE_BASE
+for start, size, name in FUNCTIONS:
+    ea = start + delta
+    if ida_funcs.get_func(ea) is None:
+        if not ida_funcs.add_func(ea, ea + size if size else ida_idaapi.BADADDR):
+            print("kakure: could not create a function at %#x" % ea)
+    if name is not None:
+        ida_name.set_name(ea, name, ida_name.SN_NOCHECK | ida_name.SN_FORCE)
+"#;
+
+/// A Python script that recreates the recovered functions in Ghidra or IDA
+///
+/// Both tools may load a PIE at a different base than kakure reports, so the
+/// script rebases every address from `image_base()` onto the tool's image base.
+/// Generated names (`FUNC_0x...`) are written as `None`, leaving the tool's own
+/// naming in place.
+fn symbol_script(analysis: &BinaryAnalysis, action: &Action) -> String {
+    // The path is data, not a comment, so a newline in it can't start a statement
+    let mut script = format!(
+        "# Functions recovered by kakure {}\nSOURCE = {}\n",
+        env!("CARGO_PKG_VERSION"),
+        serde_json::Value::from(analysis.path.as_str())
+    );
+    script += &format!("IMAGE_BASE = {:#x}\n", analysis.image_base());
+    script += "FUNCTIONS = [\n";
+    for f in analysis.functions() {
+        let name = &f.function_identifier;
+        let name = if name.is_empty() || analysis.options.naming.is_generated(name) {
+            "None".to_string()
+        } else {
+            // A JSON string is also a valid Python string literal
+            serde_json::Value::from(name.as_str()).to_string()
+        };
+        script += &format!("    ({:#x}, {:#x}, {}),\n", f.start, f.size, name);
+    }
+    script += "]\n\n";
+    script += match action {
+        Action::ExportIda => IDA_LOADER,
+        _ => GHIDRA_LOADER,
+    };
+    script
+}
 
+/// Write `contents` to `out` if given, otherwise print to stdout
+fn write_output(contents: &str, out: Option<String>, what: &str) -> Result<()> {
     if let Some(out) = out {
-        File::create(&out)?.write_all(json.as_bytes())?;
+        File::create(&out)?.write_all(contents.as_bytes())?;
         log::info!(
             "{} {}",
-            "JSON dump written to:".bright_green(),
+            format!("{what} written to:").bright_green(),
             out.bright_blue()
         );
     } else {
-        println!("{json}");
+        println!("{contents}");
     }
     Ok(())
 }
 
+/// Upper bounds (inclusive) of the size histogram buckets; the last bucket is open-ended
+const SIZE_BUCKETS: [u64; 6] = [0, 15, 63, 255, 1023, 4095];
+
+/// Aggregate function-size metrics
+#[derive(serde::Serialize)]
+struct FunctionStats {
+    total_functions: usize,
+    total_code_size: u64,
+    mean_size: f64,
+    median_size: u64,
+    /// Number of sizes the median was estimated from, when sampled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    median_sample: Option<usize>,
+    max_size: u64,
+    zero_size_functions: usize,
+    histogram: Vec<SizeBucket>,
+    /// The functions with the highest cyclomatic complexity (x86 and x86-64 only)
+    most_complex: Vec<ComplexFunction>,
+}
+
+/// Functions listed under "most complex" in the statistics
+const MOST_COMPLEX: usize = 10;
+
+/// One row of the most-complex-functions table
+#[derive(serde::Serialize, Tabled)]
+struct ComplexFunction {
+    #[tabled(rename = "Function Name")]
+    name: String,
+    #[tabled(rename = "Start Address")]
+    start: String,
+    #[tabled(rename = "Complexity")]
+    complexity: u32,
+}
+
+/// One metric/value line of the statistics table
+#[derive(Tabled)]
+struct StatRow {
+    #[tabled(rename = "Metric")]
+    metric: &'static str,
+    #[tabled(rename = "Value")]
+    value: String,
+}
+
+/// One histogram bucket, e.g. `16-63`
+#[derive(serde::Serialize, Tabled)]
+struct SizeBucket {
+    #[tabled(rename = "Size (bytes)")]
+    range: String,
+    #[tabled(rename = "Functions")]
+    count: usize,
+}
+
+impl FunctionStats {
+    /// Aggregate in one pass; only the median needs the sizes kept, and with
+    /// `sample` just a reservoir of that many
+    fn compute(analysis: &BinaryAnalysis, sample: Option<usize>) -> Self {
+        let sizes = analysis.functions().iter().map(|f| f.size);
+
+        let mut buckets = [0usize; SIZE_BUCKETS.len() + 1];
+        let (mut total_functions, mut total_code_size, mut max_size, mut zero_size_functions) =
+            (0, 0, 0, 0);
+        let mut kept = match sample {
+            Some(n) => Reservoir::new(n),
+            None => Reservoir::new(usize::MAX),
+        };
+        for size in sizes {
+            total_functions += 1;
+            total_code_size += size;
+            max_size = max_size.max(size);
+            zero_size_functions += usize::from(size == 0);
+            buckets[SIZE_BUCKETS.partition_point(|&upper| upper < size)] += 1;
+            kept.offer(size);
+        }
+
+        let mean_size = if total_functions == 0 {
+            0.0
+        } else {
+            total_code_size as f64 / total_functions as f64
+        };
+
+        let mut lower = 0;
+        let mut histogram = Vec::new();
+        for (upper, count) in SIZE_BUCKETS.iter().zip(buckets) {
+            histogram.push(SizeBucket {
+                range: if lower == *upper {
+                    format!("{upper}")
+                } else {
+                    format!("{lower}-{upper}")
+                },
+                count,
+            });
+            lower = upper + 1;
+        }
+        histogram.push(SizeBucket {
+            range: format!("{lower}+"),
+            count: buckets[SIZE_BUCKETS.len()],
+        });
+
+        let mut most_complex: Vec<ComplexFunction> = analysis
+            .functions()
+            .iter()
+            .filter_map(|f| {
+                Some(ComplexFunction {
+                    name: analysis
+                        .demangled_name(f)
+                        .unwrap_or_else(|| f.function_identifier.clone()),
+                    start: format!("0x{:016x}", f.start),
+                    complexity: analysis.complexity(f.start)?,
+                })
+            })
+            .collect();
+        // Stable, so ties stay in address order
+        most_complex.sort_by_key(|f| std::cmp::Reverse(f.complexity));
+        most_complex.truncate(MOST_COMPLEX);
+
+        let mut kept = kept.items;
+        kept.sort_unstable();
+        FunctionStats {
+            total_functions,
+            total_code_size,
+            mean_size,
+            median_size: kept.get(kept.len() / 2).copied().unwrap_or(0),
+            median_sample: sample.filter(|&n| n < total_functions).map(|_| kept.len()),
+            max_size,
+            zero_size_functions,
+            histogram,
+            most_complex,
+        }
+    }
+}
+
+/// Uniform random sample of at most `capacity` items from a stream (Algorithm R)
+///
+/// Seeded with a constant so repeated runs over the same input agree.
+struct Reservoir {
+    capacity: usize,
+    seen: u64,
+    state: u64,
+    items: Vec<u64>,
+}
+
+impl Reservoir {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            state: 0x853c_49e6_748f_ea9b,
+            items: Vec::new(),
+        }
+    }
+
+    fn offer(&mut self, item: u64) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+            return;
+        }
+        let slot = self.next_random() % self.seen;
+        if let Some(kept) = self.items.get_mut(slot as usize) {
+            *kept = item;
+        }
+    }
+
+    /// SplitMix64
+    fn next_random(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Print function-size statistics as a table, or dump them as JSON
+fn print_stats(
+    opts: &GlobalOptions,
+    status: &mut ExitStatus,
+    input: &str,
+    targets: &Targets,
+    json: bool,
+    out: Option<String>,
+    sample: Option<usize>,
+) -> Result<()> {
+    let analysis = analyze(opts, status, input, targets)?;
+    let stats = FunctionStats::compute(&analysis, sample);
+
+    if json {
+        let json = serde_json::to_string_pretty(&stats)?;
+        return write_output(&json, out, "Statistics");
+    }
+
+    println!(
+        "\n{}",
+        opts.heading("📊", "Function Statistics")
+            .bright_green()
+            .bold()
+    );
+    let summary = [
+        StatRow {
+            metric: "Total functions",
+            value: stats.total_functions.to_string(),
+        },
+        StatRow {
+            metric: "Total code size",
+            value: format!("{} bytes", stats.total_code_size),
+        },
+        StatRow {
+            metric: "Mean size",
+            value: format!("{:.1} bytes", stats.mean_size),
+        },
+        StatRow {
+            metric: "Median size",
+            value: match stats.median_sample {
+                Some(n) => format!("{} bytes (sampled from {n})", stats.median_size),
+                None => format!("{} bytes", stats.median_size),
+            },
+        },
+        StatRow {
+            metric: "Max size",
+            value: format!("{} bytes", stats.max_size),
+        },
+        StatRow {
+            metric: "Zero-size functions",
+            value: stats.zero_size_functions.to_string(),
+        },
+    ];
+    let mut table = Table::new(summary);
+    println!("{}", table.with(tabled::settings::Style::modern()));
+
+    let mut histogram = Table::new(&stats.histogram);
+    println!("{}", histogram.with(tabled::settings::Style::modern()));
+
+    if !stats.most_complex.is_empty() {
+        println!(
+            "\n{}",
+            opts.heading("🧩", "Most complex functions")
+                .bright_green()
+                .bold()
+        );
+        let mut complex = Table::new(&stats.most_complex);
+        println!("{}", complex.with(tabled::settings::Style::modern()));
+    }
+    Ok(())
+}
+
+/// Print file or section digests, one algorithm per line
+fn print_digests(
+    opts: &GlobalOptions,
+    input: &str,
+    section: Option<&str>,
+    algos: &[DigestAlgorithm],
+) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    let algos = if algos.is_empty() {
+        &DigestAlgorithm::ALL[..]
+    } else {
+        algos
+    };
+
+    let section = match section {
+        Some(name) => match analysis.get_section(name) {
+            Some(sh) => Some(sh),
+            None => bail!("Section {} not in binary", name),
+        },
+        None => None,
+    };
+
+    for algo in algos {
+        let digest = match section {
+            Some(sh) => sh.digest(*algo),
+            None => analysis.file_digest(*algo),
+        };
+        println!("{:<8} {}", algo.to_string().bright_yellow(), digest);
+    }
+    Ok(())
+}
+
+/// Print a one-screen triage overview of the input
+fn print_info(opts: &GlobalOptions, input: &str) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    let header = &analysis.header;
+    let yes_no = |present: bool| if present { "yes" } else { "no" }.to_string();
+    let has_any = |patterns: &[&str]| -> Result<String> {
+        for pattern in patterns {
+            if !analysis.get_sections_matching(pattern)?.is_empty() {
+                return Ok(yes_no(true));
+            }
+        }
+        Ok(yes_no(false))
+    };
+
+    let class = if header.is_64() { "64-bit" } else { "32-bit" };
+    // PIE executables are ET_DYN, so the type alone would call them libraries
+    let file_type = if analysis.is_pie() {
+        format!("{} (PIE)", header.file_type())
+    } else {
+        header.file_type().to_string()
+    };
+    let endianness = if header.is_little_endian() {
+        "little"
+    } else {
+        "big"
+    };
+    let fields = [
+        ("Format", header.format_name().to_string()),
+        (
+            "Machine",
+            format!("{} ({})", header.machine_name(), header.machine()),
+        ),
+        ("Class", class.to_string()),
+        ("Endianness", endianness.to_string()),
+        ("OS/ABI", header.os_abi().to_string()),
+        ("Type", file_type),
+        ("Entry point", format!("{:#x}", header.entry_point())),
+        (
+            "Build ID",
+            analysis.build_id().unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "Interpreter",
+            analysis.interpreter().unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "ABI tag",
+            analysis.abi_tag().map_or_else(
+                || "-".to_string(),
+                |(os, (major, minor, patch))| format!("{os} {major}.{minor}.{patch}"),
+            ),
+        ),
+        (
+            "Addresses",
+            match header.object_type() {
+                ObjectType::Dyn => "file-relative (slid by the load base at runtime)",
+                ObjectType::Exec => "absolute",
+                _ => "-",
+            }
+            .to_string(),
+        ),
+        ("Stripped", yes_no(analysis.is_stripped)),
+        ("Sections", analysis.sections().len().to_string()),
+        (
+            "Segments",
+            analysis
+                .segment_count()
+                .map_or_else(|| "-".to_string(), |n| n.to_string()),
+        ),
+        (
+            "Symbol table",
+            if matches!(header.format_name(), "Mach-O" | "WASM") {
+                yes_no(!analysis.is_stripped)
+            } else {
+                has_any(&[".symtab"])?
+            },
+        ),
+        ("Dynamic symbols", has_any(&[".dynsym"])?),
+        ("Unwind info", has_any(&[".eh_frame", "__TEXT,__eh_frame"])?),
+        ("Personality", personality_summary(&analysis)),
+        ("Debug info", has_any(&[".debug_*", "__DWARF,*"])?),
+        ("Split DWARF", split_dwarf_summary(&analysis)),
+        ("TLS", tls_summary(&analysis)),
+        ("Max entropy", max_code_entropy(&analysis)),
+        ("Likely packed", yes_no(analysis.is_likely_packed())),
+        (
+            "Producer",
+            analysis.producer().unwrap_or_else(|| "-".to_string()),
+        ),
+    ];
+
+    println!(
+        "{} '{}':",
+        opts.heading("🔎", "Overview of").bright_cyan().bold(),
+        input.bright_blue()
+    );
+    for (field, value) in fields {
+        println!("  {:<17} {}", format!("{field}:").bright_white(), value);
+    }
+    Ok(())
+}
+
+/// Highest entropy among executable sections, with the section's name
+fn max_code_entropy(analysis: &BinaryAnalysis) -> String {
+    analysis
+        .sections()
+        .iter()
+        .filter(|sh| sh.is_executable() && sh.raw_len() > 0)
+        .map(|sh| (sh.entropy(), &sh.name))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or_else(
+            || "-".to_string(),
+            |(entropy, name)| format!("{entropy:.2} ({name})"),
+        )
+}
+
+/// `.tdata`/`.tbss` sizes and the number of TLS symbols, `-` without TLS
+fn tls_summary(analysis: &BinaryAnalysis) -> String {
+    let Some(layout) = analysis.tls_layout() else {
+        return "-".to_string();
+    };
+    let symbols = analysis.tls_symbols().map_or(0, |symbols| symbols.len());
+    let block = layout.segment.map_or_else(String::new, |seg| {
+        format!(", {} B per thread", seg.mem_size)
+    });
+    format!(
+        ".tdata {} B, .tbss {} B{}, {} symbols",
+        layout.tdata_size, layout.tbss_size, block, symbols
+    )
+}
+
+/// Each exception personality routine with how many functions use it, `-` if none
+fn personality_summary(analysis: &BinaryAnalysis) -> String {
+    let routines = analysis.personality_routines().unwrap_or_default();
+    if routines.is_empty() {
+        return "-".to_string();
+    }
+    routines
+        .iter()
+        .map(|p| {
+            let name = p
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{:#x}", p.address));
+            format!("{} ({} functions)", name, p.functions)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `N units (M .dwo found)` for a `-gsplit-dwarf` build, `-` otherwise
+fn split_dwarf_summary(analysis: &BinaryAnalysis) -> String {
+    let units = analysis.split_units();
+    if units.is_empty() {
+        return "-".to_string();
+    }
+    let found = units.iter().filter(|u| u.path.is_some()).count();
+    format!("{} units ({} .dwo found)", units.len(), found)
+}
+
 /// Table for ELF sections
 #[derive(Tabled)]
 struct SectionRow {
@@ -248,24 +2496,87 @@ struct SectionRow {
     vma: String,
     #[tabled(rename = "Size (bytes)")]
     size: String,
+    #[tabled(rename = "Flags")]
+    flags: String,
+}
+
+/// One mapping row of `core-info`
+#[derive(Tabled)]
+struct MappingRow {
+    #[tabled(rename = "Start")]
+    start: String,
+    #[tabled(rename = "End")]
+    end: String,
+    #[tabled(rename = "File Offset")]
+    file_offset: String,
+    #[tabled(rename = "Path")]
+    path: String,
+}
+
+/// Print a core dump's threads (the crashing one first) and its file mappings
+fn core_info(opts: &GlobalOptions, input: &str) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    if analysis.header.object_type() != ObjectType::Core {
+        bail!("{} is not an ELF core dump", input);
+    }
+
+    println!(
+        "\n{}",
+        opts.heading("💥", &format!("Threads in '{}':", input))
+            .bright_green()
+            .bold()
+    );
+    for thread in analysis.core_threads() {
+        println!(
+            "  pid {:<8} signal {:<3} pc {}",
+            thread.pid,
+            thread.signal,
+            thread
+                .pc
+                .map_or_else(|| "?".to_string(), |pc| format!("{:#x}", pc))
+        );
+    }
+
+    println!(
+        "\n{}",
+        opts.heading("🗺", &format!("Mapped files in '{}':", input))
+            .bright_green()
+            .bold()
+    );
+    let rows: Vec<_> = analysis
+        .mapped_files()
+        .into_iter()
+        .map(|m| MappingRow {
+            start: format!("0x{:016x}", m.start),
+            end: format!("0x{:016x}", m.end),
+            file_offset: format!("{:#x}", m.file_offset),
+            path: m.path,
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    println!("{}", table.with(tabled::settings::Style::modern()));
+    Ok(())
 }
 
 /// List all ELF sections (pretty table)
-fn list_sections(input: &str) -> Result<()> {
-    let analysis = BinaryAnalysis::open(input)?;
+fn list_sections(opts: &GlobalOptions, input: &str) -> Result<()> {
+    let analysis = open_input(opts, input)?;
 
     println!(
         "\n{}",
-        format!("📦 Sections in '{}':", input).bright_green().bold()
+        opts.heading("📦", &format!("Sections in '{}':", input))
+            .bright_green()
+            .bold()
     );
 
     let rows: Vec<_> = analysis
-        .section_headers
+        .sections()
         .iter()
         .map(|sh| SectionRow {
             name: sh.name.clone(),
             vma: format!("0x{:016x}", sh.vma),
             size: format!("{}", sh.size),
+            flags: sh.flags_string(),
         })
         .collect();
 
@@ -276,30 +2587,80 @@ fn list_sections(input: &str) -> Result<()> {
     Ok(())
 }
 
-/// Placeholder for listing symbols
-fn list_symbols(input: &str) -> Result<()> {
-    let analysis = BinaryAnalysis::open(input)?;
+/// List the symbol table, or `.dynsym` for a stripped binary
+fn list_symbols(opts: &GlobalOptions, input: &str) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    let symbols = analysis.symbols()?;
     println!(
-        "{} '{}':",
-        "🔣 Symbols in".bright_cyan().bold(),
-        input.bright_blue()
+        "{} '{}' ({}):",
+        opts.heading("🔣", "Symbols in").bright_cyan().bold(),
+        input.bright_blue(),
+        symbols.table
     );
 
-    let strtab = analysis.get_section(".strtab");
-    if let Some(str_data) = strtab {
-        for sym in analysis.symbols()? {
-            let st_type = (sym.st_info) & 0xF;
-            let symbol_name = sym.name_from_symtab(&str_data.raw_data())?;
-            println!(
-                "  {:<30} value={} size={} type={}",
-                symbol_name.bright_white(),
-                format!("0x{:016x}", sym.st_value).bright_yellow(),
-                sym.st_size,
-                st_type
-            );
-        }
-    } else {
-        bail!("Strtab not in binary");
+    for sym in &symbols.entries {
+        let st_type = (sym.st_info) & 0xF;
+        let symbol_name = sym.name_from_symtab(symbols.strtab)?;
+        println!(
+            "  {:<30} value={} size={} type={} bind={} vis={}",
+            symbol_name.bright_white(),
+            format!("0x{:016x}", sym.st_value).bright_yellow(),
+            sym.st_size,
+            st_type,
+            sym.binding(),
+            sym.visibility()
+        );
+    }
+    Ok(())
+}
+
+/// Print the names of the symbols the input references but does not define
+fn list_undefined_symbols(opts: &GlobalOptions, input: &str) -> Result<()> {
+    let analysis = open_input(opts, input)?;
+    let names = analysis.undefined_symbols();
+    println!(
+        "{} '{}' ({}):",
+        opts.heading("🔗", "Undefined symbols in")
+            .bright_cyan()
+            .bold(),
+        input.bright_blue(),
+        names.len()
+    );
+    for name in names {
+        println!("  {}", name.bright_white());
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dumped_confidence_is_rounded() {
+        let elf = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../kakure-core/tests/fixtures/x86_32_symtab.elf"
+        ));
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+
+        let json = serde_json::to_string(&function_views(&analysis, false)).unwrap();
+        assert!(json.starts_with(r#"[{"name":"_start","start":134512724,"#));
+        assert_eq!(json.matches(r#""confidence":0.95}"#).count(), 2);
+    }
+
+    #[test]
+    fn symbol_script_quotes_the_input_path() {
+        let elf = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../kakure-core/tests/fixtures/x86_32_symtab.elf"
+        ));
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.path = "evil\nimport os".to_string();
+
+        let script = symbol_script(&analysis, &Action::ExportIda);
+        assert!(script.contains("SOURCE = \"evil\\nimport os\"\n"));
+        assert!(!script.lines().any(|line| line == "import os"));
+    }
+}
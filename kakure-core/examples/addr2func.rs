@@ -0,0 +1,37 @@
+//! Map addresses to the functions containing them
+//!
+//! ```text
+//! cargo run -p kakure-core --example addr2func -- /bin/ls 0x4a10 0x5000
+//! ```
+
+use anyhow::{bail, Context, Result};
+use kakure_core::BinaryAnalysis;
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        bail!("usage: addr2func <binary> <address>...");
+    };
+
+    let mut analysis = BinaryAnalysis::open(&path)?;
+    analysis.analyze_eh_frame()?.analyze_symtab()?;
+    analysis.identify_entry_point().sort_functions();
+
+    for arg in args {
+        let hex = arg.trim_start_matches("0x");
+        let addr =
+            u64::from_str_radix(hex, 16).with_context(|| format!("bad address '{}'", arg))?;
+
+        match analysis.function_at(addr) {
+            Some(f) => println!(
+                "{:#x} {}+{:#x} ({} bytes)",
+                addr,
+                f.function_identifier,
+                f.offset_of(addr).unwrap_or_default(),
+                f.size
+            ),
+            None => println!("{:#x} ??", addr),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Load arbitrary bytes and run every analyzer over them
+//!
+//! ```text
+//! cargo +nightly fuzz run from_bytes -- -max_len=65536
+//! ```
+//!
+//! Malformed input must come back as `Err` (or an empty analysis), never a panic.
+
+#![no_main]
+
+use kakure_core::BinaryAnalysis;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut analysis) = BinaryAnalysis::from_bytes(data.to_vec()) else {
+        return;
+    };
+    let _ = analysis.validate();
+    let _ = analysis.analyze_eh_frame();
+    let _ = analysis.analyze_symtab();
+    let _ = analysis.analyze_dynsym();
+    let _ = analysis.analyze_text_heuristic();
+    let _ = analysis.analyze_call_graph();
+    analysis.identify_entry_point().sort_functions();
+
+    for f in analysis.functions.clone() {
+        let _ = analysis.function_bytes(&f);
+        let _ = analysis.cfi_rules(f.start);
+    }
+    let _ = analysis.personality_routines();
+    let _ = analysis.notes();
+    let _ = analysis.symbols();
+});
@@ -0,0 +1,234 @@
+use goblin::elf::header::{EM_386, EM_AARCH64, EM_ARM, EM_X86_64};
+use goblin::elf::reloc::{
+    R_386_IRELATIVE, R_AARCH64_IRELATIVE, R_ARM_IRELATIVE, R_X86_64_IRELATIVE,
+};
+use iced_x86::{Decoder, DecoderOptions, Instruction, Mnemonic};
+
+/// Instruction set of the analyzed code, derived from the header's `e_machine`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+    /// Any other `e_machine` value
+    Other(u16),
+}
+
+impl Arch {
+    pub fn from_machine(machine: u16) -> Self {
+        match machine {
+            EM_386 => Arch::X86,
+            EM_X86_64 => Arch::X86_64,
+            EM_ARM => Arch::Arm,
+            EM_AARCH64 => Arch::Aarch64,
+            other => Arch::Other(other),
+        }
+    }
+
+    /// The ELF `e_machine` value for this architecture
+    pub fn machine(self) -> u16 {
+        match self {
+            Arch::X86 => EM_386,
+            Arch::X86_64 => EM_X86_64,
+            Arch::Arm => EM_ARM,
+            Arch::Aarch64 => EM_AARCH64,
+            Arch::Other(machine) => machine,
+        }
+    }
+
+    /// Name of DWARF register number `register` (as used by CFI), e.g. `rsp` for 7 on x86-64
+    pub fn dwarf_register_name(self, register: u16) -> Option<&'static str> {
+        let register = gimli::Register(register);
+        match self {
+            Arch::X86 => gimli::X86::register_name(register),
+            Arch::X86_64 => gimli::X86_64::register_name(register),
+            Arch::Arm => gimli::Arm::register_name(register),
+            Arch::Aarch64 => gimli::AArch64::register_name(register),
+            Arch::Other(_) => None,
+        }
+    }
+
+    /// Architecture-specific analysis hooks
+    ///
+    /// Architectures kakure knows nothing about get an implementation with no
+    /// prologues and no decoder, so analyzers find nothing rather than failing.
+    pub fn support(self) -> &'static dyn ArchSupport {
+        match self {
+            Arch::X86 => &X86Support { bitness: 32 },
+            Arch::X86_64 => &X86Support { bitness: 64 },
+            Arch::Arm => &ArmSupport,
+            Arch::Aarch64 => &Aarch64Support,
+            Arch::Other(_) => &NoSupport,
+        }
+    }
+}
+
+/// A byte sequence that commonly opens a function on a given architecture.
+pub struct Prologue {
+    pub bytes: &'static [u8],
+    /// Required alignment of the match within the section (1 for x86).
+    pub align: usize,
+}
+
+/// What the analyzers need to know about one instruction set
+///
+/// Every method has a conservative default, so an architecture only implements
+/// what kakure can actually do for it. Decoding goes through `iced-x86`, which
+/// limits [`ArchSupport::decoder`] to x86 for now.
+pub trait ArchSupport: Sync {
+    /// Byte patterns that commonly open a function
+    fn prologues(&self) -> &'static [Prologue] {
+        &[]
+    }
+
+    /// Alignment every instruction (and so every function start) has
+    fn instruction_alignment(&self) -> usize {
+        1
+    }
+
+    /// Length of a frame-setup instruction at the start of `window` that a fixed
+    /// byte pattern can't express, e.g. AArch64 `stp x29, x30, [sp, #-N]!`
+    fn frame_setup_len(&self, _window: &[u8]) -> Option<usize> {
+        None
+    }
+
+    /// A decoder over `data` mapped at `ip`, or `None` if kakure cannot decode this architecture
+    fn decoder<'a>(&self, _data: &'a [u8], _ip: u64) -> Option<Decoder<'a>> {
+        None
+    }
+
+    /// Whether `insn`, produced by [`ArchSupport::decoder`], is a call
+    fn is_call(&self, _insn: &Instruction) -> bool {
+        false
+    }
+
+    /// The `R_*_IRELATIVE` relocation type that marks a GOT slot filled by an ifunc resolver
+    fn irelative_type(&self) -> Option<u32> {
+        None
+    }
+
+    /// `value`, read from a pointer slot, with any authentication bits removed
+    ///
+    /// Only AArch64 signs pointers (PAC); elsewhere the value is returned as is.
+    fn strip_pointer_auth(&self, value: u64) -> u64 {
+        value
+    }
+}
+
+// push rbp; mov rbp, rsp
+const X86_64_FRAME: Prologue = Prologue {
+    bytes: &[0x55, 0x48, 0x89, 0xe5],
+    align: 1,
+};
+// endbr64
+const X86_64_ENDBR: Prologue = Prologue {
+    bytes: &[0xf3, 0x0f, 0x1e, 0xfa],
+    align: 1,
+};
+// push ebp; mov ebp, esp
+const X86_FRAME: Prologue = Prologue {
+    bytes: &[0x55, 0x89, 0xe5],
+    align: 1,
+};
+// endbr32
+const X86_ENDBR: Prologue = Prologue {
+    bytes: &[0xf3, 0x0f, 0x1e, 0xfb],
+    align: 1,
+};
+// paciasp
+const AARCH64_PACIASP: Prologue = Prologue {
+    bytes: &[0x3f, 0x23, 0x03, 0xd5],
+    align: 4,
+};
+// bti c
+const AARCH64_BTI_C: Prologue = Prologue {
+    bytes: &[0x5f, 0x24, 0x03, 0xd5],
+    align: 4,
+};
+
+/// x86 and x86-64, told apart by decoder bitness
+struct X86Support {
+    bitness: u32,
+}
+
+impl ArchSupport for X86Support {
+    fn prologues(&self) -> &'static [Prologue] {
+        if self.bitness == 64 {
+            &[X86_64_ENDBR, X86_64_FRAME]
+        } else {
+            &[X86_ENDBR, X86_FRAME]
+        }
+    }
+
+    fn decoder<'a>(&self, data: &'a [u8], ip: u64) -> Option<Decoder<'a>> {
+        Some(Decoder::with_ip(
+            self.bitness,
+            data,
+            ip,
+            DecoderOptions::NONE,
+        ))
+    }
+
+    fn is_call(&self, insn: &Instruction) -> bool {
+        insn.mnemonic() == Mnemonic::Call
+    }
+
+    fn irelative_type(&self) -> Option<u32> {
+        Some(if self.bitness == 64 {
+            R_X86_64_IRELATIVE
+        } else {
+            R_386_IRELATIVE
+        })
+    }
+}
+
+struct Aarch64Support;
+
+impl ArchSupport for Aarch64Support {
+    fn prologues(&self) -> &'static [Prologue] {
+        &[AARCH64_PACIASP, AARCH64_BTI_C]
+    }
+
+    fn instruction_alignment(&self) -> usize {
+        4
+    }
+
+    /// Matches `stp x29, x30, [sp, #-N]!`, which opens most AArch64 frames.
+    fn frame_setup_len(&self, window: &[u8]) -> Option<usize> {
+        let insn = u32::from_le_bytes(window.get(..4)?.try_into().unwrap());
+        (insn & 0xffc0_7fff == 0xa980_7bfd).then_some(4)
+    }
+
+    fn irelative_type(&self) -> Option<u32> {
+        Some(R_AARCH64_IRELATIVE)
+    }
+
+    /// Clears the PAC signature and tag byte above the virtual address bits
+    ///
+    /// Bit 55 picks the address range: user pointers (clear) get the high bits
+    /// zeroed, kernel pointers (set) get them filled with ones.
+    fn strip_pointer_auth(&self, value: u64) -> u64 {
+        let va_mask = (1u64 << AARCH64_VA_BITS) - 1;
+        if value & (1 << 55) == 0 {
+            value & va_mask
+        } else {
+            value | !va_mask
+        }
+    }
+}
+
+/// Virtual address bits of AArch64 pointers; 48 is the Linux and macOS default
+const AARCH64_VA_BITS: u32 = 48;
+
+struct ArmSupport;
+
+impl ArchSupport for ArmSupport {
+    fn irelative_type(&self) -> Option<u32> {
+        Some(R_ARM_IRELATIVE)
+    }
+}
+
+struct NoSupport;
+
+impl ArchSupport for NoSupport {}
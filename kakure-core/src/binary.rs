@@ -1,32 +1,187 @@
-use crate::eh_frame::parse_eh_frame;
-use crate::header::elf::Elf64Ehdr;
-use crate::header::Header;
-use crate::symtab::{parse_symtab_64, Elf64Sym};
-use crate::{FunctionSignature, KSection, PlatformType};
+use crate::call_graph::{
+    jump_table_targets, scan_calls, scan_jump_tables, scan_plt_stubs, CallFormat,
+    FunctionCallGraph, JumpTable,
+};
+use crate::dynsym::{gnu_hash_symbol_count, sysv_hash_symbol_count};
+use crate::eh_frame::{parse_cfi_rows, parse_eh_frame, parse_eh_frame_hdr, CfiRow};
+use crate::header::elf::{Elf32Ehdr, Elf64Ehdr};
+use crate::header::macho::MachHeader;
+use crate::header::raw::RawHeader;
+use crate::header::wasm::{WasmHeader, WASM_MAGIC};
+use crate::header::{Header, ObjectType};
+use crate::heuristic::parse_prologues;
+use crate::macho::parse_macho_symtab;
+use crate::symtab::{parse_data_symbols_64, parse_symtab_64, DataSymbol, Elf32Sym, Elf64Sym};
+use crate::wasm::{parse_wasm_functions, wasm_start_offset};
+use crate::{Arch, ArchSupport, DigestAlgorithm, FunctionSignature, KSection, PlatformType};
 use anyhow::Result;
 use anyhow::{anyhow, bail};
-use gimli::{NativeEndian, UnwindSection};
+use gimli::RunTimeEndian;
+use goblin::elf::dynamic::DF_1_PIE;
+use goblin::elf::section_header::{SHF_ALLOC, SHF_EXECINSTR, SHF_TLS, SHN_XINDEX, SHT_STRTAB};
+use goblin::elf::sym::STT_GNU_IFUNC;
 use goblin::Object;
-use std::collections::HashMap;
-use std::io::{self, Read, Seek, SeekFrom};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Read;
+use std::ops::Range;
+
+mod annotations;
+mod carve;
+mod compressed;
+mod control_flow;
+mod core_dump;
+mod debug_file;
+mod dump;
+mod format;
+mod function_index;
+mod notes;
+mod options;
+mod personality;
+mod producer;
+mod provider;
+mod report;
+mod split_dwarf;
+mod split_parts;
+mod summary;
+mod symbol_index;
+mod tls;
+
+pub use annotations::{Annotation, Annotations};
+pub use core_dump::{CoreThread, MappedFile};
+pub use format::Format;
+pub use function_index::FunctionIndex;
+pub use notes::ElfNote;
+pub use options::AnalysisOptions;
+pub use personality::PersonalityRoutine;
+pub use provider::FunctionProvider;
+pub use split_dwarf::SplitUnit;
+pub use summary::AnalysisSummary;
+pub use symbol_index::{IndexedSymbol, SymbolIndex};
+pub use tls::{TlsLayout, TlsSegment};
 
 pub struct BinaryAnalysis {
     pub functions: Vec<FunctionSignature>,
     pub path: String,
-    pub section_headers: Vec<KSection>,
+    section_headers: Vec<KSection>,
     pub is_stripped: bool,
-    pub header: Box<Elf64Ehdr>,
+    pub header: Box<dyn Header>,
+    pub options: AnalysisOptions,
     raw_buffer: Vec<u8>,
-    section_map: HashMap<String, Vec<u8>>,
+    function_sources: HashMap<u64, FunctionSource>,
+    call_graph: Vec<FunctionCallGraph>,
+    /// PLT stub and GOT slot addresses mapped to `name@plt` / `name@got`
+    import_names: HashMap<u64, String>,
+    /// Descriptions of functions whose end differs between sources
+    boundary_conflicts: Vec<String>,
+    /// Calling convention per function start, filled in with the call graph
+    calling_conventions: HashMap<u64, CallFormat>,
+    /// Analyst comments per function start, filled in by `apply_annotations`
+    comments: HashMap<u64, String>,
+    /// Total slide `rebase` added to every reported address
+    load_bias: i64,
+    /// Custom function sources registered with `add_provider`
+    providers: Vec<Box<dyn FunctionProvider>>,
+}
+
+/// Where a discovered function came from; higher variants win when sources disagree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FunctionSource {
+    Heuristic = 0, // Lowest priority
+    EhFrame = 1,
+    CallGraph = 2,
+    DynSym = 3,
+    SymTab = 4, // Highest priority
+    Manual = 5, // For entry point and user-defined
+}
+
+impl std::str::FromStr for FunctionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "heuristic" => Ok(FunctionSource::Heuristic),
+            "eh_frame" => Ok(FunctionSource::EhFrame),
+            "call_graph" => Ok(FunctionSource::CallGraph),
+            "dynsym" => Ok(FunctionSource::DynSym),
+            "symtab" => Ok(FunctionSource::SymTab),
+            "manual" => Ok(FunctionSource::Manual),
+            _ => Err(format!("Unknown function source: {}", s)),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum FunctionSource {
-    EhFrame = 0, // Lowest priority
-    CallGraph = 1,
-    DynSym = 2,
-    SymTab = 3, // Highest priority
-    Manual = 4, // For entry point and user-defined
+impl FunctionSource {
+    /// Confidence in boundaries reported by this source alone
+    pub fn confidence(self) -> f32 {
+        match self {
+            FunctionSource::Heuristic => 0.3,
+            FunctionSource::CallGraph => 0.5,
+            FunctionSource::EhFrame => 0.8,
+            FunctionSource::DynSym => 0.9,
+            FunctionSource::SymTab => 0.95,
+            FunctionSource::Manual => 1.0,
+        }
+    }
+}
+
+/// Index of the section header string table
+///
+/// When the index does not fit in `e_shstrndx` it holds `SHN_XINDEX` and the real
+/// index is in section 0's `sh_link`.
+fn shstrndx(elf: &goblin::elf::Elf) -> usize {
+    match elf.header.e_shstrndx as u32 {
+        SHN_XINDEX => elf
+            .section_headers
+            .first()
+            .map_or(0, |sh| sh.sh_link as usize),
+        index => index as usize,
+    }
+}
+
+/// Combine two independent confidences that agree on the same boundaries
+fn agreeing_confidence(a: f32, b: f32) -> f32 {
+    1.0 - (1.0 - a) * (1.0 - b)
+}
+
+impl std::fmt::Display for FunctionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FunctionSource::Heuristic => "heuristic",
+            FunctionSource::EhFrame => "eh_frame",
+            FunctionSource::CallGraph => "call_graph",
+            FunctionSource::DynSym => "dynsym",
+            FunctionSource::SymTab => "symtab",
+            FunctionSource::Manual => "manual",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The ELF symbol table a [`Symbols`] was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolTable {
+    /// `.symtab`, the full static symbol table
+    Symtab,
+    /// `.dynsym`, the exported and imported symbols kept in stripped binaries
+    Dynsym,
+}
+
+impl std::fmt::Display for SymbolTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolTable::Symtab => write!(f, ".symtab"),
+            SymbolTable::Dynsym => write!(f, ".dynsym"),
+        }
+    }
+}
+
+/// Symbol records returned by `BinaryAnalysis::symbols`, tagged with their table
+#[derive(Debug, Clone)]
+pub struct Symbols<'a> {
+    pub table: SymbolTable,
+    pub entries: Vec<Elf64Sym>,
+    /// String table the entries' `st_name` offsets point into
+    pub strtab: &'a [u8],
 }
 
 #[derive(Debug, Clone)]
@@ -37,54 +192,147 @@ struct FunctionEntry {
 impl BinaryAnalysis {
     /// Load a binary file
     pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::open_as(path, None)
+    }
+
+    /// Load a binary already held in memory
+    pub fn from_bytes(buf: Vec<u8>) -> Result<Self> {
+        Self::from_bytes_as(buf, None)
+    }
+
+    /// Load a binary file, parsing it as `format` instead of autodetecting when given
+    pub fn open_as<P: AsRef<std::path::Path>>(path: P, format: Option<Format>) -> Result<Self> {
         let mut file = std::fs::File::open(&path)?;
         let mut buf = Vec::new();
         file.read_to_end(&mut buf)?;
 
-        let obj = Object::parse(&buf)?;
+        let mut analysis = Self::from_bytes_as(buf, format)?;
+        analysis.path = path.as_ref().display().to_string();
+        Ok(analysis)
+    }
+
+    /// Load a binary already held in memory, parsing it as `format` when given
+    pub fn from_bytes_as(buf: Vec<u8>, format: Option<Format>) -> Result<Self> {
         let buf_len = buf.len();
         let mut cursor = std::io::Cursor::new(&buf);
 
-        let (header, sections, stripped) = match obj {
-            Object::Elf(elf) => Self::parse_elf(&mut cursor, elf, buf_len)?,
-            Object::PE(pe) => Self::parse_pe(&mut cursor, pe)?,
-            _ => return Err(anyhow!("Malformed binary")),
+        let (header, sections, stripped) = match format {
+            // goblin doesn't know WebAssembly, so recognize it by its magic first
+            None if buf.starts_with(&WASM_MAGIC) => Self::parse_wasm(&mut cursor)?,
+            None => match Object::parse(&buf) {
+                Ok(Object::Elf(elf)) => Self::parse_elf(&mut cursor, elf, buf_len)?,
+                Ok(Object::PE(pe)) => Self::parse_pe(&mut cursor, pe)?,
+                Ok(Object::Mach(goblin::mach::Mach::Binary(macho))) => Self::parse_macho(macho)?,
+                Ok(Object::Mach(goblin::mach::Mach::Fat(_))) => {
+                    bail!("Fat Mach-O binaries are not supported; extract a single architecture first")
+                }
+                Ok(_) | Err(_) => bail!(
+                    "Unrecognized or malformed binary; for headerless input try `--format raw --base 0x...`"
+                ),
+            },
+            Some(Format::Elf32 | Format::Elf64) => {
+                let elf = goblin::elf::Elf::parse(&buf)?;
+                if elf.is_64 != (format == Some(Format::Elf64)) {
+                    bail!(
+                        "Input is a {}-bit ELF, not {}",
+                        if elf.is_64 { 64 } else { 32 },
+                        format.unwrap()
+                    );
+                }
+                Self::parse_elf(&mut cursor, elf, buf_len)?
+            }
+            Some(Format::MachO) => Self::parse_macho(goblin::mach::MachO::parse(&buf, 0)?)?,
+            Some(Format::Wasm) => Self::parse_wasm(&mut cursor)?,
+            Some(Format::Raw { base, machine }) => return Self::open_raw(buf, base, machine),
         };
 
-        let section_map: HashMap<String, Vec<u8>> = sections
-            .iter()
-            .map(|x| (x.name.clone(), x.raw_data().clone()))
-            .collect();
-
-        Ok(Self {
+        let analysis = Self {
             functions: Vec::new(),
-            path: path.as_ref().display().to_string(),
+            path: "<memory>".to_string(),
             section_headers: sections,
             is_stripped: stripped,
             header,
+            options: AnalysisOptions::default(),
             raw_buffer: buf,
-            section_map,
+            function_sources: HashMap::new(),
+            call_graph: Vec::new(),
+            import_names: HashMap::new(),
+            boundary_conflicts: Vec::new(),
+            calling_conventions: HashMap::new(),
+            comments: HashMap::new(),
+            load_bias: 0,
+            providers: Vec::new(),
+        };
+        for issue in analysis.validate() {
+            log::warn!("{}", issue);
+        }
+
+        Ok(analysis)
+    }
+
+    /// Load container-less machine code (e.g. dumped shellcode) mapped at `base`
+    ///
+    /// The bytes become a single executable `.raw` section; `machine` is an ELF
+    /// `e_machine` value (e.g. `EM_X86_64`) used by architecture-aware analyzers.
+    pub fn open_raw(bytes: Vec<u8>, base: u64, machine: u16) -> Result<Self> {
+        if bytes.is_empty() {
+            bail!("Raw blob is empty");
+        }
+
+        let section = KSection {
+            name: ".raw".to_string(),
+            vma: base,
+            size: bytes.len() as u64,
+            file_offset: 0,
+            flags: (SHF_ALLOC | SHF_EXECINSTR) as u64,
+            raw_data: PlatformType::Unknown(bytes.clone()),
+        };
+        Ok(Self {
+            functions: Vec::new(),
+            path: "<raw>".to_string(),
+            section_headers: vec![section],
+            is_stripped: true,
+            header: Box::new(RawHeader {
+                machine,
+                base_address: base,
+            }),
+            options: AnalysisOptions::default(),
+            raw_buffer: bytes,
+            function_sources: HashMap::new(),
+            call_graph: Vec::new(),
+            import_names: HashMap::new(),
+            boundary_conflicts: Vec::new(),
+            calling_conventions: HashMap::new(),
+            comments: HashMap::new(),
+            load_bias: 0,
+            providers: Vec::new(),
         })
     }
 
-    fn get_function_map(&mut self) -> HashMap<u64, FunctionEntry> {
-        let function_map: HashMap<u64, FunctionEntry> = self
+    fn get_function_map(&mut self) -> BTreeMap<u64, FunctionEntry> {
+        let function_map: BTreeMap<u64, FunctionEntry> = self
             .functions
             .drain(..)
             .map(|sig| {
-                // Infer source for compatibility
-                let inferred_source = if sig.function_identifier.starts_with("FUNC_") {
-                    FunctionSource::EhFrame
-                } else if sig.function_identifier == "entry" {
-                    FunctionSource::Manual
-                } else {
-                    FunctionSource::SymTab
-                };
+                // Infer source for compatibility when it wasn't recorded
+                let source = self
+                    .function_sources
+                    .get(&sig.start)
+                    .copied()
+                    .unwrap_or_else(|| {
+                        if self.options.naming.is_generated(&sig.function_identifier) {
+                            FunctionSource::EhFrame
+                        } else if sig.function_identifier == "entry" {
+                            FunctionSource::Manual
+                        } else {
+                            FunctionSource::SymTab
+                        }
+                    });
                 (
-                    sig.start,
+                    sig.id(),
                     FunctionEntry {
                         signature: sig,
-                        source: inferred_source,
+                        source,
                     },
                 )
             })
@@ -92,15 +340,60 @@ impl BinaryAnalysis {
 
         function_map
     }
+
+    /// Replace the functions with the map's entries, which come out in address order
+    fn set_function_map(&mut self, function_map: BTreeMap<u64, FunctionEntry>) {
+        self.function_sources = function_map
+            .iter()
+            .map(|(start, e)| (*start, e.source))
+            .collect();
+        self.functions = function_map.into_values().map(|e| e.signature).collect();
+    }
     /// Add functions with priority-based deduplication
     fn add_functions(&mut self, new_functions: Vec<FunctionSignature>, source: FunctionSource) {
         let mut function_map = self.get_function_map();
+        let tolerance = self.options.boundary_tolerance;
+        let conflicts = &mut self.boundary_conflicts;
 
-        for new_sig in new_functions {
-            let start = new_sig.start;
+        for mut new_sig in new_functions {
+            debug_assert_eq!(
+                new_sig.end,
+                new_sig.start.saturating_add(new_sig.size),
+                "{} built without FunctionSignature::new",
+                new_sig.function_identifier
+            );
+            let start = new_sig.id();
+            new_sig.confidence = source.confidence();
             function_map
                 .entry(start)
                 .and_modify(|existing| {
+                    // Only .eh_frame knows about landing pads; keep that whichever source wins
+                    let has_exception_handling =
+                        existing.signature.has_exception_handling || new_sig.has_exception_handling;
+                    // A different source finding the same boundaries backs up the result
+                    let agrees = source != existing.source && existing.signature.end == new_sig.end;
+                    // Sizeless starts (heuristic, call graph) say nothing about the end
+                    let sized = existing.signature.size > 0 && new_sig.size > 0;
+                    if source != existing.source
+                        && sized
+                        && existing.signature.end.abs_diff(new_sig.end) > tolerance
+                    {
+                        conflicts.push(format!(
+                            "{:#x} ({}): {} ends at {:#x}, {} at {:#x}",
+                            start,
+                            new_sig.function_identifier,
+                            existing.source,
+                            existing.signature.end,
+                            source,
+                            new_sig.end
+                        ));
+                    }
+                    if agrees {
+                        new_sig.confidence =
+                            agreeing_confidence(existing.signature.confidence, new_sig.confidence);
+                        existing.signature.confidence = new_sig.confidence;
+                    }
+
                     if source > existing.source {
                         log::debug!(
                             "Replacing function at {:#x}: {} ({:?}) -> {} ({:?})",
@@ -112,7 +405,11 @@ impl BinaryAnalysis {
                         );
                         existing.signature = new_sig.clone();
                         existing.source = source;
+                    } else if source == existing.source && new_sig.is_ifunc {
+                        // An ifunc symbol aliasing its resolver's own symbol still marks it
+                        existing.signature.is_ifunc = true;
                     }
+                    existing.signature.has_exception_handling = has_exception_handling;
                 })
                 .or_insert(FunctionEntry {
                     signature: new_sig,
@@ -120,8 +417,7 @@ impl BinaryAnalysis {
                 });
         }
 
-        self.functions = function_map.into_values().map(|e| e.signature).collect();
-        self.functions.sort_by_key(|f| f.start);
+        self.set_function_map(function_map);
     }
 
     /// Parse ELF format
@@ -129,20 +425,33 @@ impl BinaryAnalysis {
         cursor: &mut std::io::Cursor<&Vec<u8>>,
         elf: goblin::elf::Elf,
         buf_len: usize,
-    ) -> Result<(Box<Elf64Ehdr>, Vec<KSection>, bool)> {
-        let elf_hdr = Elf64Ehdr::from_reader(cursor)?;
-        let mut header = Box::new(elf_hdr);
+    ) -> Result<(Box<dyn Header>, Vec<KSection>, bool)> {
+        let header: Box<dyn Header> = if elf.is_64 {
+            Box::new(Elf64Ehdr::from_reader(cursor)?)
+        } else {
+            Box::new(Elf32Ehdr::from_reader(cursor)?)
+        };
 
-        let has_sections = elf.header.e_shnum > 0 && elf.header.e_shoff != 0;
+        // With 0xff00 or more sections e_shnum is 0 and the real count is section 0's
+        // sh_size, which goblin has already applied when reading section_headers
+        let has_sections =
+            elf.header.e_shoff != 0 && (elf.header.e_shnum > 0 || elf.section_headers.len() > 1);
         let has_programs = elf.header.e_phnum > 0 && elf.header.e_phoff != 0;
 
         let (sections, stripped) = if has_sections {
             log::info!("Has section headers (not stripped)");
-            let sections = elf
+            let shstrndx = shstrndx(&elf);
+            let valid_shstrtab = elf
                 .section_headers
-                .iter()
-                .map(|sh| KSection::from_goblin_sh(cursor, sh, &elf).expect("Failed"))
-                .collect::<Vec<_>>();
+                .get(shstrndx)
+                .is_some_and(|sh| shstrndx != 0 && sh.sh_type == SHT_STRTAB);
+            if !valid_shstrtab {
+                log::warn!(
+                    "e_shstrndx {} does not point at a string table; section names will be missing",
+                    shstrndx
+                );
+            }
+            let sections = Self::read_sections(cursor, &elf)?;
             (sections, false)
         } else if has_programs {
             log::warn!("Stripped binary; using program headers");
@@ -155,77 +464,755 @@ impl BinaryAnalysis {
         Ok((header, sections, stripped))
     }
 
+    /// Read every section's bytes sequentially through the shared cursor
+    #[cfg(not(feature = "parallel"))]
+    fn read_sections(
+        cursor: &mut std::io::Cursor<&Vec<u8>>,
+        elf: &goblin::elf::Elf,
+    ) -> Result<Vec<KSection>> {
+        let sections = elf
+            .section_headers
+            .iter()
+            .map(|sh| KSection::from_goblin_sh(cursor, sh, elf))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(sections)
+    }
+
+    /// Read every section's bytes in parallel, each task using its own cursor over the buffer
+    #[cfg(feature = "parallel")]
+    fn read_sections(
+        cursor: &mut std::io::Cursor<&Vec<u8>>,
+        elf: &goblin::elf::Elf,
+    ) -> Result<Vec<KSection>> {
+        use rayon::prelude::*;
+
+        let buf: &[u8] = cursor.get_ref();
+        let sections = elf
+            .section_headers
+            .par_iter()
+            .map(|sh| KSection::from_goblin_sh(&mut std::io::Cursor::new(buf), sh, elf))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        Ok(sections)
+    }
+
+    /// Parse Mach-O format
+    fn parse_macho(macho: goblin::mach::MachO) -> Result<(Box<dyn Header>, Vec<KSection>, bool)> {
+        let header = Box::new(MachHeader::from_goblin(&macho));
+        let sections = KSection::from_goblin_macho(&macho)?;
+        let stripped = macho.symbols().next().is_none();
+        Ok((header, sections, stripped))
+    }
+
+    /// Parse a WebAssembly module
+    ///
+    /// A module without a `name` custom section counts as stripped.
+    fn parse_wasm(
+        cursor: &mut std::io::Cursor<&Vec<u8>>,
+    ) -> Result<(Box<dyn Header>, Vec<KSection>, bool)> {
+        let header = WasmHeader::from_reader(cursor)?;
+        let buf: &[u8] = cursor.get_ref();
+        let sections = KSection::from_wasm(buf)?;
+        let stripped = !sections.iter().any(|s| s.name == "name");
+        let header = WasmHeader {
+            entry: wasm_start_offset(buf)?.unwrap_or(0),
+            ..header
+        };
+        Ok((Box::new(header), sections, stripped))
+    }
+
     /// Parse PE format
     fn parse_pe(
         _cursor: &mut std::io::Cursor<&Vec<u8>>,
         _pe: goblin::pe::PE,
-    ) -> Result<(Box<Elf64Ehdr>, Vec<KSection>, bool)> {
-        todo!()
+    ) -> Result<(Box<dyn Header>, Vec<KSection>, bool)> {
+        bail!("PE parsing is not implemented yet")
     }
 
-    /// Analyze functions from .eh_frame
-    pub fn analyze_eh_frame(&mut self) -> Result<&mut Self> {
-        let base_address = self
+    /// Check the parsed layout for sections that overlap in the file or in memory
+    ///
+    /// Returns one message per overlap. Address ranges are only compared between
+    /// allocated, non-TLS sections, since `.tbss` legitimately shares addresses with
+    /// whatever follows it.
+    pub fn validate(&self) -> Vec<String> {
+        let file_ranges = self.section_headers.iter().map(|s| (s.file_range(), s));
+        let vma_ranges = self
             .section_headers
             .iter()
-            .find(|sh| sh.name == ".eh_frame")
-            .map(|sh| sh.vma)
-            .unwrap_or(0);
-
-        if let Some(data) = self.section_map.get(".eh_frame") {
-            let functions = parse_eh_frame(data, base_address)?;
-            log::info!("Found {} functions in .eh_frame", functions.len());
-            self.add_functions(functions, FunctionSource::EhFrame);
+            .filter(|s| s.is_alloc() && s.flags & SHF_TLS as u64 == 0)
+            .map(|s| (s.vma_range(), s));
+
+        let mut issues = find_overlaps(file_ranges, "file range");
+        issues.extend(find_overlaps(vma_ranges, "address range"));
+        issues
+    }
+
+    /// Fail clearly when there are no section bytes to analyze (e.g. a dump reloaded without data)
+    fn require_section_data(&self) -> Result<()> {
+        if self.section_headers.is_empty() {
+            bail!(
+                "No section data available for {}; if it was loaded from a JSON dump, re-dump with --with-data",
+                self.path
+            );
+        }
+        Ok(())
+    }
+
+    /// The file's byte order, for reading DWARF and unwind sections with gimli
+    pub(crate) fn dwarf_endian(&self) -> RunTimeEndian {
+        if self.header.is_little_endian() {
+            RunTimeEndian::Little
         } else {
+            RunTimeEndian::Big
+        }
+    }
+
+    /// Analyze functions from .eh_frame
+    pub fn analyze_eh_frame(&mut self) -> Result<&mut Self> {
+        self.require_section_data()?;
+        if self.get_section(".eh_frame").is_none() {
             log::warn!(".eh_frame not found");
+            return Ok(self);
+        }
+        self.analyze_eh_frame_in(".eh_frame")
+    }
+
+    /// Analyze functions from unwind data in the section called `section_name`
+    ///
+    /// For `.eh_frame`-format CFI that was renamed, relocated, or located by hand
+    /// in a binary without section names. Every section of that name is read (a
+    /// relocatable object may have several). Unlike [`Self::analyze_eh_frame`], a
+    /// missing section is an error. On a rebased analysis the functions come out at
+    /// rebased addresses, whichever pointer encoding the FDEs use.
+    pub fn analyze_eh_frame_in(&mut self, section_name: &str) -> Result<&mut Self> {
+        self.require_section_data()?;
+        let mut functions = Vec::new();
+        let mut found = 0;
+        for section in self.sections_named(section_name) {
+            // Absolute (`DW_EH_PE_absptr`) locations hold file addresses, so a rebased
+            // section is read at its file address and the functions slid afterwards
+            let parsed = parse_eh_frame(
+                section.raw_data(),
+                self.file_address(section.vma),
+                self.dwarf_endian(),
+                self.options.max_eh_frame_entries,
+                &self.options.naming,
+            )?;
+            functions.extend(parsed.into_iter().map(|mut f| {
+                f.start = self.slid_address(f.start);
+                f.end = self.slid_address(f.end);
+                f
+            }));
+            found += 1;
+        }
+        if found == 0 {
+            bail!("{} not found", section_name);
         }
 
+        if found > 1 {
+            log::info!(
+                "Found {} functions in {} {} sections",
+                functions.len(),
+                found,
+                section_name
+            );
+        } else {
+            log::info!("Found {} functions in {}", functions.len(), section_name);
+        }
+        self.add_functions(functions, FunctionSource::EhFrame);
+
         Ok(self)
     }
 
+    /// Function starts listed in the `.eh_frame_hdr` search table, in ascending order
+    ///
+    /// Much cheaper than `analyze_eh_frame` when only starts are needed, and useful to
+    /// cross-check it. Does not register any functions.
+    pub fn eh_frame_hdr_starts(&self) -> Result<Vec<u64>> {
+        self.require_section_data()?;
+        let section = self
+            .get_section(".eh_frame_hdr")
+            .ok_or_else(|| anyhow!(".eh_frame_hdr not found"))?;
+        let address_size = if self.header.is_64() { 8 } else { 4 };
+        let starts = parse_eh_frame_hdr(
+            section.raw_data(),
+            self.file_address(section.vma),
+            self.dwarf_endian(),
+            address_size,
+        )?;
+        Ok(starts
+            .into_iter()
+            .map(|start| self.slid_address(start))
+            .collect())
+    }
+
+    /// The unwind table `.eh_frame` gives the function containing `addr`
+    ///
+    /// `addr` may be any address the function's FDE covers, not only its start.
+    /// One row per address range, with the CFA rule and the rules for restoring
+    /// each saved register; register numbers are DWARF ones (see
+    /// `Arch::dwarf_register_name`). Errors when no FDE covers `addr`.
+    pub fn cfi_rules(&self, addr: u64) -> Result<Vec<CfiRow>> {
+        self.require_section_data()?;
+        let mut result = Err(anyhow!(".eh_frame not found"));
+        for section in self.sections_named(".eh_frame") {
+            result = parse_cfi_rows(
+                section.raw_data(),
+                self.file_address(section.vma),
+                self.dwarf_endian(),
+                self.file_address(addr),
+            );
+            if result.is_ok() {
+                break;
+            }
+        }
+        let mut rows = result?;
+        for row in &mut rows {
+            row.start = self.slid_address(row.start);
+            row.end = self.slid_address(row.end);
+        }
+        Ok(rows)
+    }
+
     /// Analyze functions from .symtab
     pub fn analyze_symtab(&mut self) -> Result<&mut Self> {
-        let section_map: HashMap<String, &Vec<u8>> = self
-            .section_headers
-            .iter()
-            .map(|x| (x.name.clone(), x.raw_data()))
-            .collect();
+        self.require_section_data()?;
+        let symtab = self.get_section_data(".symtab");
+        let strtab = self.get_section_data(".strtab");
 
-        let symtab = section_map.get(".symtab");
-        let strtab = section_map.get(".strtab");
+        match (symtab, strtab) {
+            (Some(symtab_data), Some(strtab_data)) => {
+                let symtabs = self.read_symbols(self.cap_symbols(symtab_data, ".symtab"))?;
+                let functions = parse_symtab_64(symtabs, strtab_data, &self.options.naming)?;
+                log::info!("Found {} functions in .symtab", functions.len());
+                self.add_functions(functions, FunctionSource::SymTab);
+            }
+            (Some(_), None) => {
+                log::warn!(".symtab is present but .strtab is missing; symbol names cannot be resolved")
+            }
+            (None, _) if self.get_section(".dynsym").is_some() => log::warn!(
+                ".symtab not found (stripped binary); only dynamic symbols in .dynsym/.dynstr remain"
+            ),
+            (None, _) => log::warn!(".symtab not found"),
+        }
 
-        if let (Some(symtab_data), Some(strtab_data)) = (symtab, strtab) {
-            let symtabs = Elf64Sym::from_section(&symtab_data)?;
-            let functions = parse_symtab_64(symtabs, strtab_data)?;
-            log::info!("Found {} functions in .symtab", functions.len());
-            self.add_functions(functions, FunctionSource::SymTab);
-        } else {
-            log::warn!(".symtab or .strtab not found");
+        Ok(self)
+    }
+
+    /// Analyze functions from the Mach-O `LC_SYMTAB` symbol table
+    pub fn analyze_macho_symtab(&mut self) -> Result<&mut Self> {
+        self.require_section_data()?;
+        if self.header.format_name() != "Mach-O" {
+            bail!("{} is not a Mach-O binary", self.path);
         }
 
+        let macho = goblin::mach::MachO::parse(&self.raw_buffer, 0)?;
+        let functions = parse_macho_symtab(&macho, &self.section_headers, &self.options.naming)?;
+        log::info!("Found {} functions in LC_SYMTAB", functions.len());
+        self.add_functions(functions, FunctionSource::SymTab);
+
+        Ok(self)
+    }
+
+    /// Analyze functions from a WebAssembly module's code and `name` sections
+    pub fn analyze_wasm(&mut self) -> Result<&mut Self> {
+        self.require_section_data()?;
+        if self.header.format_name() != "WASM" {
+            bail!("{} is not a WebAssembly module", self.path);
+        }
+
+        let functions = parse_wasm_functions(&self.raw_buffer, &self.options.naming)?;
+        log::info!("Found {} functions in the code section", functions.len());
+        self.add_functions(functions, FunctionSource::SymTab);
+
         Ok(self)
     }
 
     /// Analyze functions from .dynsym
     pub fn analyze_dynsym(&mut self) -> Result<&mut Self> {
-        log::warn!(".dynsym analysis not implemented");
+        self.require_section_data()?;
+        let Some(dynsym) = self.get_section(".dynsym") else {
+            log::warn!(".dynsym not found (statically linked binary?)");
+            return Ok(self);
+        };
+        let Some(dynstr) = self.get_section_data(".dynstr") else {
+            log::warn!(
+                ".dynsym is present but .dynstr is missing; symbol names cannot be resolved"
+            );
+            return Ok(self);
+        };
+
+        let symbols = self.read_symbols(self.cap_symbols(self.dynsym_data(dynsym), ".dynsym"))?;
+        let functions = parse_symtab_64(symbols, dynstr, &self.options.naming)?;
+        log::info!("Found {} functions in .dynsym", functions.len());
+        self.add_functions(functions, FunctionSource::DynSym);
+
+        Ok(self)
+    }
+
+    /// The first `options.max_symbols` records of the symbol table `data`, warning when that truncates it
+    fn cap_symbols<'a>(&self, data: &'a [u8], table: &str) -> &'a [u8] {
+        let entsize = if self.header.is_64() {
+            Elf64Sym::SIZE
+        } else {
+            Elf32Sym::SIZE
+        };
+        match self.options.max_symbols {
+            Some(max) if data.len() / entsize > max => {
+                log::warn!(
+                    "{} has {} symbols; reading only the first {} (max_symbols)",
+                    table,
+                    data.len() / entsize,
+                    max
+                );
+                &data[..max * entsize]
+            }
+            _ => data,
+        }
+    }
+
+    /// The `.dynsym` records, sized by `.gnu.hash` or `.hash` when one is present
+    ///
+    /// The hash tables bound the symbol count independently of the section header,
+    /// so a wrong `sh_size` neither drops symbols nor reads past the table. Without
+    /// them the section size is used, rounded down to whole records.
+    fn dynsym_data<'a>(&'a self, dynsym: &'a KSection) -> &'a [u8] {
+        let little_endian = self.header.is_little_endian();
+        let entsize = if self.header.is_64() {
+            Elf64Sym::SIZE
+        } else {
+            Elf32Sym::SIZE
+        };
+
+        let hashed_count = self
+            .get_section_data(".gnu.hash")
+            .and_then(|data| gnu_hash_symbol_count(data, self.header.is_64(), little_endian))
+            .or_else(|| {
+                self.get_section_data(".hash")
+                    .and_then(|data| sysv_hash_symbol_count(data, little_endian))
+            });
+
+        let section_data = dynsym.raw_data().as_slice();
+        let whole_records = &section_data[..section_data.len() / entsize * entsize];
+        let Some(count) = hashed_count else {
+            return whole_records;
+        };
+
+        let Some(len) = count.checked_mul(entsize) else {
+            log::warn!(
+                "Hash table claims {} dynamic symbols, more than fit in memory; using the .dynsym header",
+                count
+            );
+            return whole_records;
+        };
+        if len != section_data.len() {
+            log::warn!(
+                ".dynsym header covers {} symbols but the hash table counts {}; trusting the hash table",
+                section_data.len() / entsize,
+                count
+            );
+        }
+        let start = dynsym.file_offset as usize;
+        let data = start
+            .checked_add(len)
+            .and_then(|end| self.raw_buffer.get(start..end));
+        match data {
+            Some(data) => data,
+            None => &section_data[..len.min(section_data.len()) / entsize * entsize],
+        }
+    }
+
+    /// Discover candidate function starts by scanning executable sections for prologues
+    ///
+    /// This is a last-resort source for stripped binaries: candidates carry no size and
+    /// have the lowest priority, so any other analyzer overrides them.
+    pub fn analyze_text_heuristic(&mut self) -> Result<&mut Self> {
+        self.require_section_data()?;
+        let arch = self.arch().support();
+        let functions: Vec<FunctionSignature> = self
+            .code_sections()
+            .into_iter()
+            .flat_map(|sh| parse_prologues(sh.raw_data(), sh.vma, arch, &self.options.naming))
+            .collect();
+
+        log::info!(
+            "Found {} candidate functions by prologue scan",
+            functions.len()
+        );
+        self.add_functions(functions, FunctionSource::Heuristic);
+
+        Ok(self)
+    }
+
+    /// Build the call graph by sweeping executable sections for calls
+    ///
+    /// Calls into PLT stubs or through GOT slots are resolved to the imported symbol.
+    /// Jumps through switch tables add an edge to every target outside the jumping
+    /// function (see `jump_table_edges`). Call targets inside executable code that no
+    /// other analyzer found are registered as functions. Only x86 and x86-64 are decoded.
+    pub fn analyze_call_graph(&mut self) -> Result<&mut Self> {
+        self.require_section_data()?;
+        let arch = self.arch();
+        self.import_names = self.resolve_imports(arch.support());
+
+        let is_plt = |sh: &KSection| sh.name.starts_with(".plt");
+        self.call_graph = self
+            .code_sections()
+            .into_iter()
+            .filter(|sh| !is_plt(sh))
+            .flat_map(|sh| scan_calls(sh.raw_data(), sh.vma, arch.support()))
+            .collect();
+        log::info!("Found {} call edges", self.call_graph.len());
+
+        let jump_edges = self.jump_table_edges(arch.support());
+        self.call_graph.extend(jump_edges);
+        self.call_graph.sort_by_key(|edge| edge.source_call);
+
+        let mut targets: Vec<u64> = self
+            .call_graph
+            .iter()
+            .map(|edge| edge.jump_to)
+            .filter(|target| !self.import_names.contains_key(target))
+            .filter(|target| {
+                self.section_headers.iter().any(|sh| {
+                    self.is_code_section(sh)
+                        && !is_plt(sh)
+                        && sh.vma <= *target
+                        && *target < sh.vma.saturating_add(sh.raw_len() as u64)
+                })
+            })
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+
+        let functions: Vec<FunctionSignature> = targets
+            .into_iter()
+            .map(|start| FunctionSignature::new(self.options.naming.name(start), start, 0))
+            .collect();
+        log::info!("Found {} call targets", functions.len());
+        self.add_functions(functions, FunctionSource::CallGraph);
+
+        self.calling_conventions = self
+            .functions
+            .iter()
+            .filter_map(|f| {
+                let code = self.function_bytes(f).map(|data| (data, f.start));
+                Some((f.start, CallFormat::infer(arch, code)?))
+            })
+            .collect();
+
+        Ok(self)
+    }
+
+    /// Edges for jumps through switch tables, read from `.rodata` (or wherever the table lives)
+    ///
+    /// A table's length isn't recorded anywhere, so entries are read until one points
+    /// outside executable code or the next table begins. Targets inside the function
+    /// doing the jump are ordinary case labels and are dropped; the rest (tail calls
+    /// dispatched through the table) become edges. When no analyzer has bounded the
+    /// jumping function yet, every target is kept.
+    fn jump_table_edges(&self, arch: &dyn ArchSupport) -> Vec<FunctionCallGraph> {
+        let tables: Vec<_> = self
+            .code_sections()
+            .into_iter()
+            .filter(|sh| !sh.name.starts_with(".plt"))
+            .flat_map(|sh| scan_jump_tables(sh.raw_data(), sh.vma, arch))
+            .collect();
+        let mut table_starts: Vec<u64> = tables.iter().map(|t| t.table).collect();
+        table_starts.sort_unstable();
+        table_starts.dedup();
+
+        let mut edges = Vec::new();
+        let mut resolved = 0;
+        for table in &tables {
+            let targets = self.read_jump_table(table, &table_starts);
+            if !targets.is_empty() {
+                resolved += 1;
+            }
+            let container = self.function_at(table.jump).filter(|f| f.size > 0);
+            edges.extend(
+                targets
+                    .into_iter()
+                    .filter(|&target| !container.is_some_and(|f| f.contains(target)))
+                    .map(|jump_to| FunctionCallGraph {
+                        source_call: table.jump,
+                        jump_to,
+                    }),
+            );
+        }
+        edges.sort_unstable_by_key(|edge| (edge.source_call, edge.jump_to));
+        edges.dedup();
+
+        log::info!(
+            "Resolved {} of {} jump tables, adding {} edges",
+            resolved,
+            tables.len(),
+            edges.len()
+        );
+        edges
+    }
+
+    /// The targets of `table`, read up to the first entry outside executable code
+    ///
+    /// `table_starts` (sorted) bounds the read at the next table, so adjacent
+    /// tables aren't read as one.
+    fn read_jump_table(&self, table: &JumpTable, table_starts: &[u64]) -> Vec<u64> {
+        let is_code = |addr: u64| {
+            self.section_headers.iter().any(|sh| {
+                self.is_code_section(sh)
+                    && sh.vma <= addr
+                    && addr < sh.vma.saturating_add(sh.raw_len() as u64)
+            })
+        };
+
+        let Some(section) = self.section_at(table.table) else {
+            return Vec::new();
+        };
+        let next_table = table_starts[table_starts.partition_point(|&s| s <= table.table)..]
+            .first()
+            .copied()
+            .unwrap_or(u64::MAX);
+        let start = (table.table - section.vma) as usize;
+        let end = (next_table.min(section.vma.saturating_add(section.raw_len() as u64))
+            - section.vma) as usize;
+        match section.raw_data().get(start..end) {
+            Some(data) => jump_table_targets(data, table, is_code),
+            None => Vec::new(),
+        }
+    }
+
+    /// Calling convention of the function starting at `start`, once `analyze_call_graph` ran
+    pub fn calling_convention(&self, start: u64) -> Option<CallFormat> {
+        self.calling_conventions.get(&start).copied()
+    }
+
+    /// Name PLT stubs and GOT slots after the dynamic symbols their relocations refer to
+    ///
+    /// Slots filled by an ifunc resolver (`*_IRELATIVE`, or an `STT_GNU_IFUNC` symbol)
+    /// are named `resolver@ifunc`, since the call lands on whatever the resolver picked.
+    fn resolve_imports(&self, arch: &dyn ArchSupport) -> HashMap<u64, String> {
+        let mut names = HashMap::new();
+        if self.header.format_name() != "ELF" {
+            return names;
+        }
+        let Ok(elf) = goblin::elf::Elf::parse(&self.raw_buffer) else {
+            return names;
+        };
+
+        // Slot address -> (symbol or resolver name, whether the slot holds an ifunc resolver)
+        let mut slots: HashMap<u64, (String, bool)> = HashMap::new();
+        for reloc in elf
+            .pltrelocs
+            .iter()
+            .chain(elf.dynrelas.iter())
+            .chain(elf.dynrels.iter())
+        {
+            if arch.irelative_type() == Some(reloc.r_type) {
+                // No symbol: the addend is the resolver's address
+                let resolver = reloc.r_addend.unwrap_or(0) as u64;
+                let name = self
+                    .functions
+                    .binary_search_by_key(&resolver, |f| f.start)
+                    .map(|i| self.functions[i].function_identifier.clone())
+                    .unwrap_or_else(|_| self.options.naming.name(resolver));
+                slots.insert(reloc.r_offset, (name, true));
+            } else if reloc.r_sym != 0 {
+                let Some(sym) = elf.dynsyms.get(reloc.r_sym) else {
+                    continue;
+                };
+                match elf.dynstrtab.get_at(sym.st_name) {
+                    Some(name) if !name.is_empty() => {
+                        let is_ifunc = sym.st_type() == STT_GNU_IFUNC;
+                        slots.insert(reloc.r_offset, (name.to_string(), is_ifunc));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for sh in self
+            .section_headers
+            .iter()
+            .filter(|sh| sh.name.starts_with(".plt"))
+        {
+            for (stub, slot) in scan_plt_stubs(sh.raw_data(), sh.vma, arch) {
+                if let Some((name, is_ifunc)) = slots.get(&slot) {
+                    let suffix = if *is_ifunc { "ifunc" } else { "plt" };
+                    names.insert(stub, format!("{}@{}", name, suffix));
+                }
+            }
+        }
+        for (slot, (name, is_ifunc)) in slots {
+            let suffix = if is_ifunc { "ifunc" } else { "got" };
+            names.insert(slot, format!("{}@{}", name, suffix));
+        }
+
+        names
+    }
+
+    /// Call edges found by `analyze_call_graph`
+    pub fn call_graph(&self) -> &[FunctionCallGraph] {
+        &self.call_graph
+    }
+
+    /// Names of everything `func` calls that resolves to an import or a known function
+    ///
+    /// Each name appears once, in the order of the first call to it.
+    pub fn calls_from(&self, func: &FunctionSignature) -> Vec<String> {
+        let mut calls: Vec<String> = Vec::new();
+        for edge in self
+            .call_graph
+            .iter()
+            .filter(|edge| func.contains(edge.source_call))
+        {
+            let name = self.import_names.get(&edge.jump_to).cloned().or_else(|| {
+                self.functions
+                    .binary_search_by_key(&edge.jump_to, |f| f.start)
+                    .ok()
+                    .map(|i| self.functions[i].function_identifier.clone())
+            });
+            if let Some(name) = name.filter(|name| !calls.contains(name)) {
+                calls.push(name);
+            }
+        }
+        calls
+    }
+
+    /// Whether the scanners treat `sh` as code, per `options.only_executable_sections`
+    fn is_code_section(&self, sh: &KSection) -> bool {
+        if self.options.only_executable_sections {
+            sh.is_executable()
+        } else {
+            sh.is_alloc()
+        }
+    }
+
+    /// Code sections (see [`Self::is_code_section`]) that have bytes to scan
+    ///
+    /// Sections that are present but carry no file data (NOBITS, zero file size) are
+    /// skipped, with a warning for executable ones so an empty result is never silent.
+    fn code_sections(&self) -> Vec<&KSection> {
+        self.section_headers
+            .iter()
+            .filter(|sh| self.is_code_section(sh))
+            .filter(|sh| {
+                if sh.raw_len() == 0 && sh.is_executable() {
+                    log::warn!("Section {} is present but empty; nothing to scan", sh.name);
+                }
+                sh.raw_len() > 0
+            })
+            .collect()
+    }
+
+    /// Run every analyzer that applies to this format, then name the entry point
+    ///
+    /// ELF inputs go through `.eh_frame`, `.symtab` and `.dynsym`; Mach-O through its
+    /// symbol table; WebAssembly through its code and `name` sections. Registered
+    /// providers run after those. In strict mode this fails if the sources disagreed about any
+    /// function's boundaries.
+    pub fn analyze_all(&mut self) -> Result<&mut Self> {
+        if self.header.format_name() == "Mach-O" {
+            self.analyze_macho_symtab()?;
+        } else if self.header.format_name() == "WASM" {
+            self.analyze_wasm()?;
+        } else {
+            self.analyze_eh_frame()?
+                .analyze_symtab()?
+                .analyze_dynsym()?;
+        }
+        self.analyze_providers()?;
+        self.identify_entry_point();
+        self.drop_small_functions();
+        self.check_boundaries()?;
         Ok(self)
     }
 
+    /// Functions whose end address differed between two sources by more than
+    /// `options.boundary_tolerance`, one description each
+    pub fn boundary_conflicts(&self) -> &[String] {
+        &self.boundary_conflicts
+    }
+
+    /// In strict mode, fail with every recorded boundary conflict; otherwise do nothing
+    pub fn check_boundaries(&self) -> Result<()> {
+        if !self.options.strict || self.boundary_conflicts.is_empty() {
+            return Ok(());
+        }
+        bail!(
+            "{} function boundary conflict(s) in {}:\n  {}",
+            self.boundary_conflicts.len(),
+            self.path,
+            self.boundary_conflicts.join("\n  ")
+        )
+    }
+
+    /// Record a function known from elsewhere (e.g. earlier reversing)
+    ///
+    /// It is added as `FunctionSource::Manual`, so no analyzer run before or after
+    /// replaces it. Only `identify_entry_point` touches it, renaming it to `entry`
+    /// if it starts at the entry point.
+    pub fn add_manual_function(&mut self, name: &str, start: u64, size: u64) -> &mut Self {
+        let function = FunctionSignature::new(name, start, size);
+        self.add_functions(vec![function], FunctionSource::Manual);
+        self
+    }
+
     /// Deduplicate functions (handled automatically)
     pub fn deduplicate_functions(&mut self) -> &mut Self {
         log::debug!("Deduplication handled via priority system");
         self
     }
 
+    /// Trim each function's end back to the start of the next function
+    ///
+    /// Symbol sizes sometimes include alignment padding, so a function can run into
+    /// its neighbor. Only overlaps of at most `max_overlap` bytes are clamped (all of
+    /// them with `None`), since a large one more likely means nested symbols than
+    /// padding. Sizeless starts (heuristic, call graph) say nothing about where the
+    /// previous function ends and are ignored. Run it once all sources are merged.
+    pub fn clamp_overlaps(&mut self, max_overlap: Option<u64>) -> &mut Self {
+        let mut clamped = 0;
+        let mut next_start: Option<u64> = None;
+        for f in self.functions.iter_mut().rev() {
+            if let Some(next) = next_start.filter(|&next| f.end > next) {
+                let overlap = f.end - next;
+                if max_overlap.is_none_or(|max| overlap <= max) {
+                    log::debug!(
+                        "Clamping {} at {:#x}: end {:#x} -> {:#x} ({} bytes into the next function)",
+                        f.function_identifier,
+                        f.start,
+                        f.end,
+                        next,
+                        overlap
+                    );
+                    f.end = next;
+                    f.size = next - f.start;
+                    clamped += 1;
+                } else {
+                    log::debug!(
+                        "Leaving {} at {:#x}: overlaps the next function by {} bytes",
+                        f.function_identifier,
+                        f.start,
+                        overlap
+                    );
+                }
+            }
+            if f.size > 0 {
+                next_start = Some(f.start);
+            }
+        }
+
+        log::info!("Clamped {} overlapping function ends", clamped);
+        // Clamping can take a function below the minimum size
+        self.drop_small_functions()
+    }
+
     /// Add entry point function
     pub fn identify_entry_point(&mut self) -> &mut Self {
-        let entry_addr = self.header.entry_point();
-
-        if entry_addr == 0 {
+        if self.header.entry_point() == 0 {
             log::warn!("ELF header has no entry point");
             return self;
         }
+        let entry_addr = self.slid_address(self.header.entry_point());
 
         // Build a function map to manage priorities cleanly
         let mut function_map = self.get_function_map();
@@ -241,17 +1228,17 @@ impl BinaryAnalysis {
                 entry.signature.function_identifier = "entry".to_string();
             }
             entry.source = FunctionSource::Manual;
+            entry.signature.confidence = FunctionSource::Manual.confidence();
         } else {
             // Add a new synthetic entry if it doesn’t exist
             log::info!(
                 "Entry address {:#x} not found in existing functions — adding synthetic 'entry'",
                 entry_addr
             );
+            // Size unknown, so it only covers its start
             let entry_sig = FunctionSignature {
-                function_identifier: "entry".to_string(),
-                start: entry_addr,
-                size: 0,
-                end: entry_addr, // optional: same as start, since we don’t know size
+                confidence: FunctionSource::Manual.confidence(),
+                ..FunctionSignature::new("entry", entry_addr, 0)
             };
             function_map.insert(
                 entry_addr,
@@ -263,24 +1250,329 @@ impl BinaryAnalysis {
         }
 
         // Replace functions list with updated map
-        self.functions = function_map.into_values().map(|e| e.signature).collect();
-        self.functions.sort_by_key(|f| f.start);
+        self.set_function_map(function_map);
 
         self
     }
 
+    /// The function at the header's entry point, or the synthetic `entry` if it was added elsewhere
+    pub fn entry_function(&self) -> Option<&FunctionSignature> {
+        let entry_addr = self.header.entry_point();
+        self.functions
+            .iter()
+            .find(|f| entry_addr != 0 && f.start == self.slid_address(entry_addr))
+            .or_else(|| {
+                self.functions
+                    .iter()
+                    .find(|f| f.function_identifier == "entry")
+            })
+    }
+
     /// Sort functions by address
+    ///
+    /// Analyzers keep them sorted already; this is only needed after editing
+    /// `functions` directly.
     pub fn sort_functions(&mut self) -> &mut Self {
         self.functions.sort_by_key(|f| f.start);
         self
     }
 
+    /// Hex digest of the whole input file (of nothing, for analyses reloaded from a dump)
+    pub fn file_digest(&self, algo: DigestAlgorithm) -> String {
+        algo.hex_digest(&self.raw_buffer)
+    }
+
     /// Get section by name
+    ///
+    /// ELF allows several sections with one name (common in relocatable objects);
+    /// this is the first in header order. See [`Self::sections_named`] for all of them.
     pub fn get_section(&self, name: &str) -> Option<&KSection> {
         self.section_headers.iter().find(|s| s.name == name)
     }
 
+    /// Every section called `name`, in header order
+    pub fn sections_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a KSection> {
+        self.section_headers.iter().filter(move |s| s.name == name)
+    }
+
+    /// The pointer stored at `addr`, in the binary's word size and byte order
+    ///
+    /// Authentication bits are stripped on AArch64 (see
+    /// [`ArchSupport::strip_pointer_auth`]). `None` when no allocated section holds
+    /// the bytes.
+    pub fn read_pointer(&self, addr: u64) -> Option<u64> {
+        let section = self.section_at(addr)?;
+        let offset = (addr - section.vma) as usize;
+        let width = if self.header.is_64() { 8 } else { 4 };
+        let bytes = section.raw_data().get(offset..offset.checked_add(width)?)?;
+        let value = match (width, self.header.is_little_endian()) {
+            (8, true) => u64::from_le_bytes(bytes.try_into().ok()?),
+            (8, false) => u64::from_be_bytes(bytes.try_into().ok()?),
+            (_, true) => u32::from_le_bytes(bytes.try_into().ok()?) as u64,
+            (_, false) => u32::from_be_bytes(bytes.try_into().ok()?) as u64,
+        };
+        Some(self.arch().support().strip_pointer_auth(value))
+    }
+
+    /// The address the byte at file offset `offset` is loaded at
+    ///
+    /// Translates positions in the file, such as those a core dump's `NT_FILE`
+    /// mappings give, back to addresses in this binary. `None` for bytes that no
+    /// allocated section holds.
+    pub fn vma_at_file_offset(&self, offset: u64) -> Option<u64> {
+        self.section_headers
+            .iter()
+            .filter(|sh| sh.is_alloc())
+            .find(|sh| sh.file_range().contains(&offset))
+            .map(|sh| sh.vma + (offset - sh.file_offset))
+    }
+
+    /// The allocated section whose `[vma, vma + size)` contains `vma`
+    ///
+    /// Non-allocated sections (`.comment`, `.debug_*`) all sit at address 0 and are
+    /// never returned. `.tbss` shares its addresses with the section after it, so
+    /// TLS sections only match when nothing else does.
+    pub fn section_at(&self, vma: u64) -> Option<&KSection> {
+        let mut matches = self
+            .section_headers
+            .iter()
+            .filter(|sh| sh.is_alloc() && sh.vma_range().contains(&vma));
+        let first = matches.next()?;
+        if first.flags & SHF_TLS as u64 == 0 {
+            return Some(first);
+        }
+        matches
+            .find(|sh| sh.flags & SHF_TLS as u64 == 0)
+            .or(Some(first))
+    }
+
+    /// Instruction set of the code, from the header's machine
+    pub fn arch(&self) -> Arch {
+        Arch::from_machine(self.header.machine())
+    }
+
+    /// Lowest address the image occupies once loaded at its preferred base
+    ///
+    /// The first `PT_LOAD` (rounded down to its alignment) for ELF, the first mapped
+    /// segment for Mach-O (skipping `__PAGEZERO`), and the lowest allocated section
+    /// otherwise. Disassemblers report this as the image base, so it is what
+    /// addresses need to be taken relative to when the tool rebases a PIE.
+    pub fn image_base(&self) -> u64 {
+        let base = match Object::parse(&self.raw_buffer) {
+            Ok(Object::Elf(elf)) => elf
+                .program_headers
+                .iter()
+                .filter(|ph| ph.p_type == goblin::elf::program_header::PT_LOAD)
+                .map(|ph| ph.p_vaddr & !ph.p_align.saturating_sub(1))
+                .min(),
+            Ok(Object::Mach(goblin::mach::Mach::Binary(macho))) => macho
+                .segments
+                .iter()
+                .filter(|seg| seg.filesize > 0)
+                .map(|seg| seg.vmaddr)
+                .min(),
+            _ => None,
+        };
+        match base {
+            Some(base) => base.wrapping_add_signed(self.load_bias),
+            // Section addresses already include the bias
+            None => self
+                .section_headers
+                .iter()
+                .filter(|sh| sh.is_alloc())
+                .map(|sh| sh.vma)
+                .min()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Whether this is a position-independent executable
+    ///
+    /// A PIE is an ELF `ET_DYN` object, like a shared library, that is also meant to
+    /// be run: it carries `DF_1_PIE` in `DT_FLAGS_1` or, for linkers that don't set
+    /// it, asks for an interpreter with `PT_INTERP`. Its addresses are relative to a
+    /// zero base and slide by the load base at runtime.
+    pub fn is_pie(&self) -> bool {
+        if self.header.format_name() != "ELF" || self.header.object_type() != ObjectType::Dyn {
+            return false;
+        }
+        let Ok(elf) = goblin::elf::Elf::parse(&self.raw_buffer) else {
+            return false;
+        };
+        let flagged = elf
+            .dynamic
+            .as_ref()
+            .is_some_and(|dynamic| dynamic.info.flags_1 & DF_1_PIE != 0);
+        flagged || elf.interpreter.is_some()
+    }
+
+    /// Path of the dynamic linker the binary asks for, e.g. `/lib64/ld-linux-x86-64.so.2`
+    ///
+    /// Read from the `PT_INTERP` segment, or the `.interp` section when there are no
+    /// program headers. `None` for static binaries, shared libraries and non-ELF
+    /// formats.
+    pub fn interpreter(&self) -> Option<String> {
+        if self.header.format_name() != "ELF" {
+            return None;
+        }
+        let elf = goblin::elf::Elf::parse(&self.raw_buffer).ok()?;
+        if let Some(interp) = elf.interpreter {
+            return Some(interp.to_string());
+        }
+        let data = self.get_section_data(".interp")?;
+        let path = data.split(|&b| b == 0).next().unwrap_or_default();
+        (!path.is_empty()).then(|| String::from_utf8_lossy(path).into_owned())
+    }
+
+    /// Add `slide` to every address, as the loader does to an ASLR'd image
+    ///
+    /// Meant for correlating with a running process or crash dump once analysis is
+    /// done: functions, call edges, import names and section addresses all move
+    /// together, and `image_base()` moves with them. The header's entry point is left
+    /// as recorded in the file. Fails, changing nothing, if a function or section
+    /// would end up below zero or past the top of the address space.
+    pub fn rebase(&mut self, slide: i64) -> Result<&mut Self> {
+        if slide == 0 {
+            return Ok(self);
+        }
+        let function_bounds = self.functions.iter().flat_map(|f| [f.start, f.end]);
+        let section_bounds = self
+            .section_headers
+            .iter()
+            .filter(|sh| sh.is_alloc())
+            .flat_map(|sh| {
+                let range = sh.vma_range();
+                [range.start, range.end]
+            });
+        if let Some(addr) = function_bounds
+            .chain(section_bounds)
+            .find(|addr| addr.checked_add_signed(slide).is_none())
+        {
+            bail!(
+                "Sliding by {}{:#x} would move {:#x} outside the address space",
+                if slide < 0 { "-" } else { "" },
+                slide.unsigned_abs(),
+                addr
+            );
+        }
+        let shift = |addr: u64| addr.wrapping_add_signed(slide);
+
+        for f in &mut self.functions {
+            f.start = shift(f.start);
+            f.end = shift(f.end);
+        }
+        for edge in &mut self.call_graph {
+            edge.source_call = shift(edge.source_call);
+            edge.jump_to = shift(edge.jump_to);
+        }
+        for sh in &mut self.section_headers {
+            if sh.is_alloc() {
+                sh.vma = shift(sh.vma);
+            }
+        }
+        self.function_sources = self
+            .function_sources
+            .drain()
+            .map(|(a, v)| (shift(a), v))
+            .collect();
+        self.import_names = self
+            .import_names
+            .drain()
+            .map(|(a, v)| (shift(a), v))
+            .collect();
+        self.calling_conventions = self
+            .calling_conventions
+            .drain()
+            .map(|(a, v)| (shift(a), v))
+            .collect();
+        self.comments = self.comments.drain().map(|(a, v)| (shift(a), v)).collect();
+        self.load_bias = self.load_bias.wrapping_add(slide);
+        Ok(self)
+    }
+
+    /// [`Self::rebase`] by whatever slide moves `image_base()` to `load_base`
+    /// (e.g. a PIE's first mapping in `/proc/<pid>/maps`)
+    pub fn rebase_to(&mut self, load_base: u64) -> Result<&mut Self> {
+        self.rebase(load_base.wrapping_sub(self.image_base()) as i64)
+    }
+
+    /// Total slide applied by `rebase` (0 if never rebased)
+    pub fn load_bias(&self) -> i64 {
+        self.load_bias
+    }
+
+    /// Where `addr` lies in the file's own address space, undoing any `rebase`
+    fn file_address(&self, addr: u64) -> u64 {
+        addr.wrapping_add_signed(self.load_bias.wrapping_neg())
+    }
+
+    /// Where the file address `addr` lies after any `rebase`
+    fn slid_address(&self, addr: u64) -> u64 {
+        addr.wrapping_add_signed(self.load_bias)
+    }
+
+    /// Get all sections whose name matches a glob pattern, e.g. `.debug*` or `.segment_*`
+    pub fn get_sections_matching(&self, pattern: &str) -> Result<Vec<&KSection>> {
+        let pattern = glob::Pattern::new(pattern)?;
+        Ok(self
+            .section_headers
+            .iter()
+            .filter(|s| pattern.matches(&s.name))
+            .collect())
+    }
+
+    /// Get all sections whose name matches a regular expression
+    #[cfg(feature = "regex")]
+    pub fn get_sections_matching_regex(&self, pattern: &str) -> Result<Vec<&KSection>> {
+        let re = regex::Regex::new(pattern)?;
+        Ok(self
+            .section_headers
+            .iter()
+            .filter(|s| re.is_match(&s.name))
+            .collect())
+    }
+
+    /// Raw bytes of the ELF section header string table (`.shstrtab`)
+    ///
+    /// Located through `e_shstrndx` rather than by name, so it is found even when
+    /// names fail to resolve. `None` for non-ELF input, reloaded dumps, or an
+    /// out-of-range index.
+    pub fn shstrtab(&self) -> Option<&[u8]> {
+        if self.header.format_name() != "ELF" {
+            return None;
+        }
+        let elf = goblin::elf::Elf::parse(&self.raw_buffer).ok()?;
+        let section = self.section_headers.get(shstrndx(&elf))?;
+        Some(section.raw_data().as_slice())
+    }
+
+    /// Number of program headers (ELF) or segment load commands (Mach-O)
+    ///
+    /// `None` for raw blobs and reloaded dumps, which keep no container to count from.
+    pub fn segment_count(&self) -> Option<usize> {
+        match self.header.format_name() {
+            "ELF" => goblin::elf::Elf::parse_header(&self.raw_buffer)
+                .ok()
+                .map(|ehdr| ehdr.e_phnum as usize),
+            "Mach-O" => goblin::mach::MachO::parse(&self.raw_buffer, 0)
+                .ok()
+                .map(|macho| macho.segments.len()),
+            _ => None,
+        }
+    }
+
+    /// Resolve an `sh_name` offset against the raw `.shstrtab`
+    pub fn section_name_at(&self, sh_name: usize) -> Option<&str> {
+        let bytes = self.shstrtab()?.get(sh_name..)?;
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..len]).ok()
+    }
+
     /// Get raw section data
+    ///
+    /// `None` means there is no such section; `Some(&[])` means the section exists
+    /// but has no bytes in the file (e.g. `SHT_NOBITS`). Reads the first section of
+    /// that name, like [`Self::get_section`].
     pub fn get_section_data(&self, name: &str) -> Option<&[u8]> {
         self.get_section(name).map(|x| x.raw_data().as_slice())
     }
@@ -290,25 +1582,324 @@ impl BinaryAnalysis {
         &self.functions
     }
 
-    /// Return the symbol table
-    pub fn symbols(&self) -> anyhow::Result<Vec<Elf64Sym>> {
-        let section_data = self.get_section_data(".symtab");
+    /// Access all sections, in header order
+    pub fn sections(&self) -> &[KSection] {
+        &self.section_headers
+    }
+
+    /// The innermost function containing `addr`, if any
+    ///
+    /// Relies on `functions` being sorted by start. Of nested functions (a local
+    /// label symbol inside its parent), the one starting closest below `addr` wins.
+    /// A zero-size function only matches its exact start address.
+    pub fn function_at(&self, addr: u64) -> Option<&FunctionSignature> {
+        let idx = self.functions.partition_point(|f| f.start <= addr);
+        // Nested functions ending before `addr` may start after the one holding it
+        self.functions[..idx]
+            .iter()
+            .rev()
+            .find(|f| f.contains(addr))
+    }
+
+    /// The analyzer that produced the function starting at `start`
+    pub fn function_source(&self, start: u64) -> Option<FunctionSource> {
+        self.function_sources.get(&start).copied()
+    }
+
+    /// `func`'s name demangled with `options.demangle`, or `None` if it isn't mangled
+    ///
+    /// `function_identifier` always keeps the raw symbol name.
+    pub fn demangled_name(&self, func: &FunctionSignature) -> Option<String> {
+        self.options.demangle.demangle(&func.function_identifier)
+    }
+
+    /// Drop functions smaller than `options.min_function_size`
+    ///
+    /// Functions of unknown size (sizeless starts, zero-sized symbols) say nothing
+    /// about their length and are kept, as are `FunctionSource::Manual` ones. Run by
+    /// `analyze_all` and `clamp_overlaps`; call it again after changing sizes by hand.
+    pub fn drop_small_functions(&mut self) -> &mut Self {
+        let min = self.options.min_function_size;
+        if min == 0 {
+            return self;
+        }
+        let manual: HashSet<u64> = self
+            .function_sources
+            .iter()
+            .filter(|(_, source)| **source == FunctionSource::Manual)
+            .map(|(start, _)| *start)
+            .collect();
+        let dropped =
+            self.retain_functions(|f| f.size == 0 || f.size >= min || manual.contains(&f.start));
+        log::info!("Dropped {} functions smaller than {} bytes", dropped, min);
+        self
+    }
+
+    /// Drop functions whose confidence is below `min`
+    pub fn retain_min_confidence(&mut self, min: f32) -> &mut Self {
+        let dropped = self.retain_functions(|f| f.confidence >= min);
+        log::info!("Dropped {} functions below confidence {}", dropped, min);
+        self
+    }
+
+    /// Keep only local (`STB_LOCAL`) functions when `local`, otherwise only exported ones
+    ///
+    /// Weak and unique symbols count as exported. Functions with no symbol behind
+    /// them (found through `.eh_frame` or heuristics) have no binding and are dropped
+    /// either way.
+    pub fn retain_binding(&mut self, local: bool) -> &mut Self {
+        let dropped = self.retain_functions(|f| f.binding.is_some_and(|b| b.is_local() == local));
+        log::info!(
+            "Dropped {} functions that are not {}",
+            dropped,
+            if local { "local" } else { "global" }
+        );
+        self
+    }
+
+    /// Keep the functions matching `keep`, returning how many were dropped
+    fn retain_functions(&mut self, keep: impl FnMut(&FunctionSignature) -> bool) -> usize {
+        let before = self.functions.len();
+        self.functions.retain(keep);
+        let kept: HashSet<u64> = self.functions.iter().map(|f| f.start).collect();
+        self.function_sources
+            .retain(|start, _| kept.contains(start));
+        before - self.functions.len()
+    }
+
+    /// Slice the bytes of `func` out of the section that contains it
+    ///
+    /// In relocatable objects that is the section its symbol names. `None` when no
+    /// allocated section holds all of `[start, end)` in the file (e.g. the function
+    /// lies in `.bss`-like `SHT_NOBITS` space); empty for a function whose size is
+    /// unknown. See also [`FunctionSignature::bytes`].
+    pub fn function_bytes(&self, func: &FunctionSignature) -> Option<&[u8]> {
+        let holds = |s: &&KSection| {
+            s.is_alloc()
+                && s.vma <= func.start
+                && func.end <= s.vma.saturating_add(s.raw_len() as u64)
+        };
+        let section = match func.section {
+            // Every section of a relocatable object starts at 0, so the address
+            // alone cannot tell them apart
+            Some(index) if self.header.object_type() == ObjectType::Rel => {
+                self.section_headers.get(index).filter(holds)?
+            }
+            _ => self.section_headers.iter().find(holds)?,
+        };
+        let offset = (func.start - section.vma) as usize;
+        section
+            .raw_data()
+            .get(offset..offset.checked_add(func.size as usize)?)
+    }
+
+    /// Whether the binary looks packed or encrypted
+    ///
+    /// True when some executable section has an entropy above 7.0 bits per byte and
+    /// the binary carries fewer than 16 symbols. Packers compress the real code and
+    /// strip the symbol table, so ordinary stripped binaries (low entropy) and
+    /// binaries that just embed compressed data (plenty of symbols) are not flagged.
+    pub fn is_likely_packed(&self) -> bool {
+        const ENTROPY_THRESHOLD: f64 = 7.0;
+        const MAX_SYMBOLS: usize = 16;
+
+        let high_entropy_code = self
+            .section_headers
+            .iter()
+            .filter(|sh| sh.is_executable())
+            .any(|sh| sh.entropy() > ENTROPY_THRESHOLD);
+        let symbol_count = self.symbols().map_or(0, |symbols| symbols.entries.len());
+
+        high_entropy_code && symbol_count < MAX_SYMBOLS
+    }
+
+    /// Cluster byte-identical functions (ICF candidates, duplicated template instances)
+    ///
+    /// Only groups with at least two members are returned. Functions with no size or
+    /// whose bytes are not present in the file are ignored.
+    pub fn group_identical_functions(&self) -> Vec<Vec<&FunctionSignature>> {
+        let mut by_content: HashMap<&[u8], Vec<&FunctionSignature>> = HashMap::new();
+        for func in self.functions.iter().filter(|f| f.size > 0) {
+            if let Some(bytes) = self.function_bytes(func) {
+                by_content.entry(bytes).or_default().push(func);
+            }
+        }
+
+        let mut groups: Vec<_> = by_content
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect();
+        groups.sort_by_key(|group| group[0].start);
+        groups
+    }
+
+    /// Return whatever symbols exist: `.symtab`, or `.dynsym` when it is absent
+    ///
+    /// Stripped dynamic binaries only keep `.dynsym`; the result says which table
+    /// was read. Names resolve against `Symbols::strtab` (also `symbol_strtab()`).
+    pub fn symbols(&self) -> anyhow::Result<Symbols<'_>> {
+        let (table, data, strtab) = self.symbol_sections()?;
+        Ok(Symbols {
+            table,
+            entries: self.read_symbols(data)?,
+            strtab,
+        })
+    }
 
-        if let Some(data) = section_data {
-            let symtab = Elf64Sym::from_section(&data)?;
-            return Ok(symtab);
+    /// Data (`STT_OBJECT`) symbols, e.g. for labeling GOT targets and data references
+    pub fn data_symbols(&self) -> anyhow::Result<Vec<DataSymbol>> {
+        let symbols = self.symbols()?;
+        Ok(parse_data_symbols_64(&symbols.entries, symbols.strtab))
+    }
+
+    /// Names of the undefined (`SHN_UNDEF`) symbols in `.symtab` and `.dynsym`, sorted
+    ///
+    /// These are the functions and data the binary expects another object (usually
+    /// a shared library) to provide, the ELF counterpart of a PE import table.
+    /// Version suffixes (`printf@GLIBC_2.2.5`, as newer linkers write them into
+    /// `.symtab`) are dropped, so each name appears once.
+    pub fn undefined_symbols(&self) -> Vec<String> {
+        let tables = [
+            (
+                SymbolTable::Symtab,
+                self.get_section_data(".symtab"),
+                ".strtab",
+            ),
+            (
+                SymbolTable::Dynsym,
+                self.get_section(".dynsym").map(|sh| self.dynsym_data(sh)),
+                ".dynstr",
+            ),
+        ];
+        let mut names = BTreeSet::new();
+        for (table, data, strtab_name) in tables {
+            let (Some(data), Some(strtab)) = (data, self.get_section_data(strtab_name)) else {
+                continue;
+            };
+            let symbols = self.read_all_symbols(data).unwrap_or_else(|e| {
+                log::warn!("Skipping malformed {}: {}", table, e);
+                Vec::new()
+            });
+            names.extend(
+                symbols
+                    .iter()
+                    .filter(|sym| sym.is_undefined())
+                    .filter_map(|sym| sym.raw_name(strtab).split('@').next())
+                    .filter(|name| !name.is_empty())
+                    .map(String::from),
+            );
+        }
+        names.into_iter().collect()
+    }
+
+    /// The string table for the symbols returned by `symbols()`
+    pub fn symbol_strtab(&self) -> anyhow::Result<&[u8]> {
+        let (_, _, strtab) = self.symbol_sections()?;
+        Ok(strtab)
+    }
+
+    /// Decode a symbol table section using the layout and byte order of this binary
+    ///
+    /// 32-bit symbols are widened to `Elf64Sym`.
+    fn read_symbols(&self, data: &[u8]) -> anyhow::Result<Vec<Elf64Sym>> {
+        let little_endian = self.header.is_little_endian();
+        if self.header.is_64() {
+            Ok(Elf64Sym::from_section_endian(data, little_endian)?)
         } else {
-            bail!("No.symtab in binary");
+            Ok(Elf32Sym::from_section_endian(data, little_endian)?
+                .into_iter()
+                .map(Elf64Sym::from)
+                .collect())
+        }
+    }
+
+    /// Decode every record of a symbol table section, undefined and null ones included
+    ///
+    /// 32-bit symbols are widened to `Elf64Sym`.
+    fn read_all_symbols(&self, data: &[u8]) -> anyhow::Result<Vec<Elf64Sym>> {
+        let little_endian = self.header.is_little_endian();
+        if self.header.is_64() {
+            Ok(Elf64Sym::all_from_section_endian(data, little_endian)?)
+        } else {
+            Ok(Elf32Sym::all_from_section_endian(data, little_endian)?
+                .into_iter()
+                .map(Elf64Sym::from)
+                .collect())
+        }
+    }
+
+    /// `.symtab`/`.strtab`, or `.dynsym`/`.dynstr` for stripped binaries
+    ///
+    /// `.dynsym` is sized by its hash table (see `dynsym_data`), which is all a
+    /// `DT_GNU_HASH`-only binary has to bound it.
+    fn symbol_sections(&self) -> anyhow::Result<(SymbolTable, &[u8], &[u8])> {
+        match (
+            self.get_section_data(".symtab"),
+            self.get_section_data(".strtab"),
+        ) {
+            (Some(symtab), Some(strtab)) => return Ok((SymbolTable::Symtab, symtab, strtab)),
+            (Some(_), None) => log::warn!(".symtab is present but .strtab is missing"),
+            (None, _) => {}
+        }
+
+        match (
+            self.get_section(".dynsym"),
+            self.get_section_data(".dynstr"),
+        ) {
+            (Some(dynsym), Some(dynstr)) => {
+                log::debug!("No usable .symtab; falling back to .dynsym");
+                Ok((SymbolTable::Dynsym, self.dynsym_data(dynsym), dynstr))
+            }
+            (Some(_), None) => {
+                bail!("No usable .symtab, and .dynsym is present but .dynstr is missing")
+            }
+            (None, _) => bail!("Neither .symtab nor .dynsym is present in {}", self.path),
         }
     }
 }
 
+/// Describe every range that starts before the furthest-reaching earlier range ends
+fn find_overlaps<'a>(
+    ranges: impl Iterator<Item = (Range<u64>, &'a KSection)>,
+    what: &str,
+) -> Vec<String> {
+    let mut ranges: Vec<_> = ranges.filter(|(range, _)| !range.is_empty()).collect();
+    ranges.sort_by_key(|(range, _)| (range.start, range.end));
+
+    let mut issues = Vec::new();
+    let mut furthest: Option<(Range<u64>, &KSection)> = None;
+    for (range, section) in ranges {
+        if let Some((prev_range, prev)) = &furthest {
+            if range.start < prev_range.end {
+                issues.push(format!(
+                    "Section {} {} [{:#x}, {:#x}) overlaps {} [{:#x}, {:#x})",
+                    section.name,
+                    what,
+                    range.start,
+                    range.end,
+                    prev.name,
+                    prev_range.start,
+                    prev_range.end
+                ));
+            }
+        }
+        if furthest
+            .as_ref()
+            .is_none_or(|(prev_range, _)| range.end > prev_range.end)
+        {
+            furthest = Some((range, section));
+        }
+    }
+    issues
+}
+
 // Priority system (highest to lowest):
-// 1. Manual (entry point, user-defined) - FunctionSource::Manual = 4
-// 2. SymTab (.symtab) - FunctionSource::SymTab = 3
-// 3. DynSym (.dynsym) - FunctionSource::DynSym = 2
-// 4. CallGraph (future) - FunctionSource::CallGraph = 1
-// 5. EhFrame (.eh_frame) - FunctionSource::EhFrame = 0
+// 1. Manual (entry point, user-defined) - FunctionSource::Manual = 5
+// 2. SymTab (.symtab) - FunctionSource::SymTab = 4
+// 3. DynSym (.dynsym) - FunctionSource::DynSym = 3
+// 4. CallGraph (call targets) - FunctionSource::CallGraph = 2
+// 5. EhFrame (.eh_frame) - FunctionSource::EhFrame = 1
+// 6. Heuristic (prologue scan) - FunctionSource::Heuristic = 0
 //
 // Example usage:
 // let analysis = BinaryAnalysis::open("path/to/binary")?
@@ -317,3 +1908,68 @@ impl BinaryAnalysis {
 //     .analyze_symtab()?         // Overwrites with even better names (highest priority)
 //     .identify_entry_point()    // Marks entry point (won't be overwritten)
 //     .build();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_at_looks_past_nested_functions() {
+        let code = vec![0x90; 0x20];
+        let raw = Format::Raw {
+            base: 0x8048054,
+            machine: goblin::elf::header::EM_386,
+        };
+        let mut analysis = BinaryAnalysis::from_bytes_as(code, Some(raw)).unwrap();
+        analysis.functions = vec![
+            FunctionSignature::new("outer", 0x8048054, 0x10),
+            FunctionSignature::new("inner", 0x8048056, 2),
+        ];
+
+        let name = |addr| {
+            analysis
+                .function_at(addr)
+                .map(|f| f.function_identifier.as_str())
+        };
+        assert_eq!(name(0x8048057), Some("inner"));
+        assert_eq!(name(0x8048060), Some("outer"));
+        assert_eq!(name(0x8048064), None);
+    }
+
+    #[test]
+    fn aarch64_pointers_lose_their_authentication_bits() {
+        let mut data = 0x002f_0000_0040_1000u64.to_le_bytes().to_vec();
+        // A kernel pointer keeps its high bits set
+        data.extend(0x2aff_8000_0040_1000u64.to_le_bytes());
+        let raw = Format::Raw {
+            base: 0x1000,
+            machine: goblin::elf::header::EM_AARCH64,
+        };
+        let analysis = BinaryAnalysis::from_bytes_as(data, Some(raw)).unwrap();
+        assert_eq!(analysis.read_pointer(0x1000), Some(0x401000));
+        assert_eq!(analysis.read_pointer(0x1008), Some(0xffff_8000_0040_1000));
+        assert_eq!(analysis.read_pointer(0x1010), None);
+    }
+
+    #[test]
+    fn entry_function_follows_a_rebase() {
+        let elf = include_bytes!("../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+        analysis.rebase(0x1000).unwrap();
+
+        let entry = analysis.entry_function().unwrap();
+        assert_eq!(
+            (entry.function_identifier.as_str(), entry.start),
+            ("_start", 0x8049054)
+        );
+
+        analysis.identify_entry_point();
+        let starts: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.start))
+            .collect();
+        assert_eq!(starts, [("entry", 0x8049054), ("helper", 0x804905e)]);
+    }
+}
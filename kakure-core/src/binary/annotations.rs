@@ -0,0 +1,166 @@
+use super::{FunctionEntry, FunctionSource};
+use crate::{BinaryAnalysis, FunctionSignature};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// An analyst's name and/or comment for one function
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Renames and comments kept beside a binary, keyed by function start ([`FunctionSignature::id`])
+///
+/// Stored as a JSON object whose keys are `0x`-prefixed hex addresses, e.g.
+/// `{"0x401000": {"name": "parse_header", "comment": "validates magic"}}`, so
+/// the file stays readable and diffable by hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotations {
+    pub functions: BTreeMap<u64, Annotation>,
+}
+
+impl Annotations {
+    /// Default location of the annotations for `binary`: `<binary>.kakure.json`
+    pub fn sidecar_path<P: AsRef<Path>>(binary: P) -> PathBuf {
+        let mut path = binary.as_ref().as_os_str().to_owned();
+        path.push(".kakure.json");
+        PathBuf::from(path)
+    }
+
+    /// Read an annotations file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read annotations {}", path.display()))?;
+        let raw: BTreeMap<String, Annotation> = serde_json::from_str(&text)
+            .with_context(|| format!("Malformed annotations {}", path.display()))?;
+
+        let functions = raw
+            .into_iter()
+            .map(|(key, annotation)| {
+                let hex = key.strip_prefix("0x").unwrap_or(&key);
+                let addr = u64::from_str_radix(hex, 16)
+                    .map_err(|_| anyhow!("Bad address '{}' in {}", key, path.display()))?;
+                Ok((addr, annotation))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { functions })
+    }
+
+    /// [`Self::load`], or no annotations if `path` doesn't exist yet
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Write the annotations as pretty-printed JSON, sorted by address
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        // Serialized straight from the address-ordered map, so entries stay sorted by address
+        let json = serde_json::to_string_pretty(&self)?;
+        std::fs::write(path.as_ref(), json + "\n")
+            .with_context(|| format!("Failed to write annotations {}", path.as_ref().display()))
+    }
+
+    /// Record a name and/or comment for the function at `addr`
+    ///
+    /// Fields left as `None` keep whatever was recorded before.
+    pub fn set(&mut self, addr: u64, name: Option<String>, comment: Option<String>) {
+        let annotation = self.functions.entry(addr).or_default();
+        if name.is_some() {
+            annotation.name = name;
+        }
+        if comment.is_some() {
+            annotation.comment = comment;
+        }
+    }
+}
+
+impl BinaryAnalysis {
+    /// Merge analyst annotations into the recovered functions
+    ///
+    /// Run it after the analyzers and `identify_entry_point`: a renamed function is
+    /// promoted to `FunctionSource::Manual`, so the analyst's name wins. An annotated
+    /// address no analyzer found is added as a zero-sized function when it has a name.
+    pub fn apply_annotations(&mut self, annotations: &Annotations) -> &mut Self {
+        let mut function_map = self.get_function_map();
+
+        for (&addr, annotation) in &annotations.functions {
+            if let Some(comment) = &annotation.comment {
+                self.comments.insert(addr, comment.clone());
+            }
+            let Some(name) = &annotation.name else {
+                continue;
+            };
+
+            let entry = function_map.entry(addr).or_insert_with(|| FunctionEntry {
+                signature: FunctionSignature::new(name.clone(), addr, 0),
+                source: FunctionSource::Manual,
+            });
+            entry.signature.function_identifier = name.clone();
+            entry.signature.confidence = FunctionSource::Manual.confidence();
+            entry.source = FunctionSource::Manual;
+        }
+
+        self.set_function_map(function_map);
+        self
+    }
+
+    /// Analyst comment on the function starting at `start`, once annotations were applied
+    pub fn comment(&self, start: u64) -> Option<&str> {
+        self.comments.get(&start).map(String::as_str)
+    }
+}
+
+impl Serialize for Annotations {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.functions
+                .iter()
+                .map(|(addr, annotation)| (format!("{:#x}", addr), annotation)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotations_rename_and_round_trip() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+
+        let mut annotations = Annotations::default();
+        annotations.set(0x804805e, Some("parse_header".into()), None);
+        annotations.set(0x804805e, None, Some("validates magic".into()));
+
+        let path = std::env::temp_dir().join(format!("kakure-ann-{}.json", std::process::id()));
+        annotations.save(&path).unwrap();
+        let loaded = Annotations::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, annotations);
+
+        analysis.apply_annotations(&loaded);
+        let f = analysis
+            .functions
+            .iter()
+            .find(|f| f.start == 0x804805e)
+            .unwrap();
+        assert_eq!(f.function_identifier, "parse_header");
+        assert_eq!(f.size, 6);
+        assert_eq!(
+            analysis.function_source(f.start),
+            Some(FunctionSource::Manual)
+        );
+        assert_eq!(analysis.comment(f.start), Some("validates magic"));
+    }
+}
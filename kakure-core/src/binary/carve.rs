@@ -0,0 +1,87 @@
+use super::Format;
+use crate::BinaryAnalysis;
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Seek, SeekFrom};
+
+impl BinaryAnalysis {
+    /// Load the image stored at `offset` inside a larger file (e.g. one of several
+    /// concatenated firmware images) as a binary of its own
+    ///
+    /// Reads `len` bytes, or everything up to the end of the file when `None`, and
+    /// autodetects the container within them. File offsets reported for sections are
+    /// relative to `offset`.
+    pub fn open_at_offset<P: AsRef<std::path::Path>>(
+        path: P,
+        offset: u64,
+        len: Option<u64>,
+    ) -> Result<Self> {
+        Self::open_at_offset_as(path, offset, len, None)
+    }
+
+    /// [`Self::open_at_offset`], parsing the carved image as `format` when given
+    pub fn open_at_offset_as<P: AsRef<std::path::Path>>(
+        path: P,
+        offset: u64,
+        len: Option<u64>,
+        format: Option<Format>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let mut file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let file_len = file.metadata()?.len();
+
+        if offset >= file_len {
+            bail!(
+                "Offset {:#x} is past the end of {} ({:#x} bytes)",
+                offset,
+                path.display(),
+                file_len
+            );
+        }
+        let available = file_len - offset;
+        let len = match len {
+            Some(len) if len > available => bail!(
+                "{:#x} bytes at offset {:#x} run past the end of {} ({:#x} bytes)",
+                len,
+                offset,
+                path.display(),
+                file_len
+            ),
+            Some(len) => len,
+            None => available,
+        };
+
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buf = Vec::with_capacity(len as usize);
+        file.take(len).read_to_end(&mut buf)?;
+
+        let mut analysis = Self::from_bytes_as(buf, format)
+            .with_context(|| format!("No binary at offset {:#x} of {}", offset, path.display()))?;
+        analysis.path = format!("{}@{:#x}", path.display(), offset);
+        Ok(analysis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_is_carved_from_the_middle_of_a_file() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut blob = vec![0xffu8; 0x100];
+        blob.extend_from_slice(elf);
+        blob.extend_from_slice(&[0u8; 0x40]);
+
+        let path = std::env::temp_dir().join(format!("kakure-carve-{}.bin", std::process::id()));
+        std::fs::write(&path, &blob).unwrap();
+        let carved = BinaryAnalysis::open_at_offset(&path, 0x100, Some(elf.len() as u64));
+        let past_end = BinaryAnalysis::open_at_offset(&path, 0x100, Some(blob.len() as u64));
+        std::fs::remove_file(&path).unwrap();
+
+        let mut carved = carved.unwrap();
+        carved.analyze_symtab().unwrap();
+        assert!(carved.functions.iter().any(|f| f.start == 0x804805e));
+        assert!(past_end.is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use super::Format;
+use crate::BinaryAnalysis;
+use anyhow::{anyhow, Context, Result};
+use std::io::Read;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const XZ_MAGIC: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+impl BinaryAnalysis {
+    /// Load a binary that may be stored gzip, xz or zstd compressed
+    ///
+    /// The container is recognized by its magic bytes, not the file extension, and
+    /// decompressed in memory. Uncompressed files load exactly as with [`Self::open`].
+    pub fn open_compressed<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::open_compressed_as(path, None)
+    }
+
+    /// [`Self::open_compressed`], parsing the decompressed image as `format` when given
+    pub fn open_compressed_as<P: AsRef<std::path::Path>>(
+        path: P,
+        format: Option<Format>,
+    ) -> Result<Self> {
+        let buf = std::fs::read(&path)?;
+        let buf = decompress(buf)
+            .with_context(|| format!("Failed to decompress {}", path.as_ref().display()))?;
+
+        let mut analysis = Self::from_bytes_as(buf, format)?;
+        analysis.path = path.as_ref().display().to_string();
+        Ok(analysis)
+    }
+}
+
+/// Unwrap a gzip, xz or zstd stream; anything else is returned untouched
+fn decompress(buf: Vec<u8>) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    if buf.starts_with(GZIP_MAGIC) {
+        flate2::read::MultiGzDecoder::new(buf.as_slice()).read_to_end(&mut out)?;
+    } else if buf.starts_with(XZ_MAGIC) {
+        lzma_rs::xz_decompress(&mut buf.as_slice(), &mut out).map_err(|e| anyhow!("xz: {}", e))?;
+    } else if buf.starts_with(ZSTD_MAGIC) {
+        ruzstd::decoding::StreamingDecoder::new(buf.as_slice())
+            .map_err(|e| anyhow!("zstd: {}", e))?
+            .read_to_end(&mut out)?;
+    } else {
+        return Ok(buf);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn gzip_input_is_transparently_decompressed() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(elf).unwrap();
+        let gz = gz.finish().unwrap();
+
+        assert_eq!(decompress(gz).unwrap(), elf);
+        assert_eq!(decompress(elf.to_vec()).unwrap(), elf);
+    }
+}
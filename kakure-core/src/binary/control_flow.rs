@@ -0,0 +1,41 @@
+use crate::call_graph::scan_jump_tables;
+use crate::control_flow::{basic_blocks, cyclomatic_complexity, BasicBlock};
+use crate::BinaryAnalysis;
+use std::collections::HashMap;
+
+impl BinaryAnalysis {
+    /// Basic blocks of the function starting at `func_start`, reachable from its entry
+    ///
+    /// Jumps through switch tables inside the function branch to each case label.
+    /// `None` when no function starts there, its size or bytes are unknown, or the
+    /// architecture isn't decoded (only x86 and x86-64 are).
+    pub fn basic_blocks(&self, func_start: u64) -> Option<Vec<BasicBlock>> {
+        let func = self
+            .function_at(func_start)
+            .filter(|f| f.start == func_start)?;
+        let data = self.function_bytes(func).filter(|data| !data.is_empty())?;
+        let arch = self.arch().support();
+
+        let tables = scan_jump_tables(data, func.start, arch);
+        let mut table_starts: Vec<u64> = tables.iter().map(|t| t.table).collect();
+        table_starts.sort_unstable();
+        table_starts.dedup();
+        let indirect_targets: HashMap<u64, Vec<u64>> = tables
+            .iter()
+            .map(|table| {
+                let mut targets = self.read_jump_table(table, &table_starts);
+                targets.retain(|&target| func.contains(target));
+                (table.jump, targets)
+            })
+            .collect();
+
+        basic_blocks(data, func.start, arch, &indirect_targets)
+    }
+
+    /// Cyclomatic complexity (`edges - nodes + 2`) of the function starting at `func_start`
+    ///
+    /// Computed from [`Self::basic_blocks`], and `None` whenever that is.
+    pub fn complexity(&self, func_start: u64) -> Option<u32> {
+        Some(cyclomatic_complexity(&self.basic_blocks(func_start)?))
+    }
+}
@@ -0,0 +1,141 @@
+use crate::header::ObjectType;
+use crate::{Arch, BinaryAnalysis};
+use goblin::elf::note::{NT_FILE, NT_PRSTATUS};
+
+/// A file mapped into the dumped process, from the core's `NT_FILE` note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappedFile {
+    /// First address of the mapping
+    pub start: u64,
+    /// One past the last address of the mapping
+    pub end: u64,
+    /// Offset into the file that `start` maps
+    pub file_offset: u64,
+    /// Path of the file when the process died
+    pub path: String,
+}
+
+impl MappedFile {
+    /// Offset into the mapped file of the process address `addr`, if the mapping covers it
+    pub fn file_offset_of(&self, addr: u64) -> Option<u64> {
+        (self.start..self.end)
+            .contains(&addr)
+            .then(|| self.file_offset + (addr - self.start))
+    }
+}
+
+/// One thread's state in a core dump, from its `NT_PRSTATUS` note
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreThread {
+    pub pid: u32,
+    /// Signal the thread was stopped by (`pr_cursig`), 0 if none
+    pub signal: u16,
+    /// Program counter; `None` on architectures whose register layout kakure doesn't know
+    pub pc: Option<u64>,
+}
+
+/// Bytes before `pr_reg` in `struct elf_prstatus`, and the offset of `pr_pid`
+fn prstatus_layout(is_64: bool) -> (usize, usize) {
+    if is_64 {
+        (112, 32)
+    } else {
+        (72, 24)
+    }
+}
+
+impl BinaryAnalysis {
+    /// Files mapped into the process, from the `NT_FILE` note of an ELF core dump
+    ///
+    /// Empty for anything but a core, or if the note is missing or malformed.
+    pub fn mapped_files(&self) -> Vec<MappedFile> {
+        if self.header.object_type() != ObjectType::Core {
+            return Vec::new();
+        }
+        let Some(note) = self
+            .notes()
+            .into_iter()
+            .find(|note| note.name == "CORE" && note.n_type == NT_FILE)
+        else {
+            return Vec::new();
+        };
+
+        // count, page size, then (start, end, page offset) per file, then the
+        // NUL-terminated names, all in target words
+        let word_size = if self.header.is_64() { 8 } else { 4 };
+        let word = |i: usize| self.read_uint(note.desc.get(i * word_size..)?, word_size);
+        let (Some(count), Some(page_size)) = (word(0), word(1)) else {
+            return Vec::new();
+        };
+        let Some(names_start) = usize::try_from(count)
+            .ok()
+            .and_then(|count| count.checked_mul(3 * word_size)?.checked_add(2 * word_size))
+        else {
+            return Vec::new();
+        };
+        let mut names = note
+            .desc
+            .get(names_start..)
+            .unwrap_or_default()
+            .split(|&b| b == 0);
+
+        (0..count as usize)
+            .map_while(|i| {
+                let base = 2 + i * 3;
+                Some(MappedFile {
+                    start: word(base)?,
+                    end: word(base + 1)?,
+                    file_offset: word(base + 2)?.checked_mul(page_size)?,
+                    path: String::from_utf8_lossy(names.next()?).into_owned(),
+                })
+            })
+            .collect()
+    }
+
+    /// The dumped threads, one per `NT_PRSTATUS` note; the first is the one that crashed
+    ///
+    /// The program counter is read for x86, x86-64 and AArch64. Empty for anything
+    /// but a core.
+    pub fn core_threads(&self) -> Vec<CoreThread> {
+        if self.header.object_type() != ObjectType::Core {
+            return Vec::new();
+        }
+        let is_64 = self.header.is_64();
+        let word_size = if is_64 { 8 } else { 4 };
+        let (regs, pid) = prstatus_layout(is_64);
+        // Index of the PC in `pr_reg` (`user_regs_struct` / `user_pt_regs`)
+        let pc_index = match self.arch() {
+            Arch::X86_64 => Some(16),
+            Arch::X86 => Some(12),
+            Arch::Aarch64 => Some(32),
+            _ => None,
+        };
+
+        self.notes()
+            .into_iter()
+            .filter(|note| note.name == "CORE" && note.n_type == NT_PRSTATUS)
+            .filter_map(|note| {
+                let field =
+                    |offset: usize, size: usize| self.read_uint(note.desc.get(offset..)?, size);
+                Some(CoreThread {
+                    pid: field(pid, 4)? as u32,
+                    // pr_cursig follows the three ints of pr_info
+                    signal: field(12, 2)? as u16,
+                    pc: pc_index.and_then(|i| field(regs + i * word_size, word_size)),
+                })
+            })
+            .collect()
+    }
+
+    /// The `size`-byte unsigned integer (2, 4 or 8) at the start of `bytes`, in the header's byte order
+    fn read_uint(&self, bytes: &[u8], size: usize) -> Option<u64> {
+        let mut buf = [0u8; 8];
+        let bytes = bytes.get(..size)?;
+        if self.header.is_little_endian() {
+            buf[..size].copy_from_slice(bytes);
+            Some(u64::from_le_bytes(buf))
+        } else {
+            buf[8 - size..].copy_from_slice(bytes);
+            Some(u64::from_be_bytes(buf))
+        }
+    }
+}
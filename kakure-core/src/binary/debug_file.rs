@@ -0,0 +1,93 @@
+use crate::{BinaryAnalysis, FunctionSource};
+use anyhow::{anyhow, bail, Result};
+use std::path::{Path, PathBuf};
+
+impl BinaryAnalysis {
+    /// Where a debug file for this binary sits under `debug_root` (e.g. `/usr/lib/debug`)
+    ///
+    /// `.build-id/ab/cdef....debug`, split after the first byte of the build ID as
+    /// GDB and the distros lay it out. `None` without a build ID.
+    pub fn build_id_debug_path(&self, debug_root: &Path) -> Option<PathBuf> {
+        let build_id = self.build_id().filter(|id| id.len() > 2)?;
+        let (dir, file) = build_id.split_at(2);
+        Some(
+            debug_root
+                .join(".build-id")
+                .join(dir)
+                .join(format!("{file}.debug")),
+        )
+    }
+
+    /// Merge the symbol table of the detached debug file found by build ID under `debug_root`
+    ///
+    /// The debug file keeps the `.symtab` that stripping removed, so this recovers
+    /// symbol names and sizes for stripped distro binaries. Its functions rank as
+    /// `FunctionSource::SymTab` and follow any `rebase` already applied. Fails when
+    /// the binary has no build ID or the debug file is missing, unreadable or has a
+    /// different build ID.
+    pub fn load_debug_by_buildid(&mut self, debug_root: &Path) -> Result<&mut Self> {
+        let path = self
+            .build_id_debug_path(debug_root)
+            .ok_or_else(|| anyhow!("{} has no build ID", self.path))?;
+        if !path.is_file() {
+            bail!("No debug file at {}", path.display());
+        }
+
+        let mut debug = BinaryAnalysis::open(&path)?;
+        if debug.build_id() != self.build_id() {
+            bail!(
+                "{} has build ID {}, not {}",
+                path.display(),
+                debug.build_id().as_deref().unwrap_or("(none)"),
+                self.build_id().unwrap_or_default()
+            );
+        }
+        debug.options.naming = self.options.naming.clone();
+        debug.options.max_symbols = self.options.max_symbols;
+        debug.analyze_symtab()?;
+        if self.load_bias != 0 {
+            debug.rebase(self.load_bias)?;
+        }
+
+        log::info!(
+            "Found {} functions in {}",
+            debug.functions.len(),
+            path.display()
+        );
+        self.add_functions(debug.functions, FunctionSource::SymTab);
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stripped_binary_gets_symbols_from_its_build_id_debug_file() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_buildid.elf");
+        let debug = include_bytes!("../../tests/fixtures/x86_32_buildid.debug");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        assert!(analysis.get_section(".symtab").is_none());
+
+        let root = std::env::temp_dir().join(format!("kakure-debug-{}", std::process::id()));
+        let path = analysis.build_id_debug_path(&root).unwrap();
+        assert!(path.ends_with(".build-id/9c/abaa14e5a3d29ae61369c7fb84d9b30d46ac2e.debug"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, debug).unwrap();
+
+        let result = analysis.load_debug_by_buildid(&root).map(|_| ());
+        std::fs::remove_dir_all(&root).unwrap();
+        result.unwrap();
+
+        let functions: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.start, f.size))
+            .collect();
+        assert_eq!(
+            functions,
+            [("_start", 0x8048098, 10), ("helper", 0x80480a2, 6)]
+        );
+    }
+}
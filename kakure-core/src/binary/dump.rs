@@ -0,0 +1,268 @@
+use crate::header::elf::{Elf32Ehdr, Elf64Ehdr};
+use crate::header::raw::RawHeader;
+use crate::header::Header;
+use crate::{BinaryAnalysis, FunctionSignature, FunctionSource, KSection, PlatformType};
+use anyhow::{bail, Context, Result};
+use base64::prelude::*;
+use goblin::elf::header::{
+    EI_CLASS, EI_DATA, EI_VERSION, ELFCLASS32, ELFCLASS64, ELFDATA2LSB, ELFDATA2MSB, ELFMAG,
+    EV_CURRENT,
+};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A function entry as written by the CLI's `dump-json` action
+///
+/// The dumped `end` is ignored and recomputed from `start` and `size`.
+#[derive(Deserialize)]
+struct FunctionDump {
+    name: String,
+    start: u64,
+    size: u64,
+    #[serde(default)]
+    visibility: Option<String>,
+    #[serde(default)]
+    binding: Option<String>,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    is_ifunc: bool,
+    #[serde(default)]
+    has_exception_handling: bool,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    parent: Option<String>,
+}
+
+/// A section entry as written by `dump-json --with-data`
+#[derive(Deserialize)]
+struct SectionDump {
+    name: String,
+    vma: u64,
+    size: u64,
+    file_offset: u64,
+    flags: u64,
+    data: String,
+}
+
+/// The header fields a `--with-data` snapshot records
+#[derive(Deserialize)]
+struct HeaderDump {
+    format: String,
+    machine: u16,
+    is_64: bool,
+    little_endian: bool,
+    entry: u64,
+}
+
+/// Either a plain function array or a `--with-data` snapshot object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnalysisDump {
+    Functions(Vec<FunctionDump>),
+    Snapshot {
+        path: String,
+        /// Missing from snapshots written before it was recorded
+        #[serde(default)]
+        header: Option<HeaderDump>,
+        functions: Vec<FunctionDump>,
+        sections: Vec<SectionDump>,
+    },
+}
+
+/// One input's dump, or several keyed by path
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DumpFile {
+    Single(AnalysisDump),
+    Inputs(BTreeMap<String, AnalysisDump>),
+}
+
+impl HeaderDump {
+    /// An ELF (or raw blob) header with the dumped machine, class, byte order and entry
+    fn into_header(self) -> Result<Box<dyn Header>> {
+        let mut e_ident = [0u8; 16];
+        e_ident[..4].copy_from_slice(ELFMAG);
+        e_ident[EI_CLASS] = if self.is_64 { ELFCLASS64 } else { ELFCLASS32 };
+        e_ident[EI_DATA] = if self.little_endian {
+            ELFDATA2LSB
+        } else {
+            ELFDATA2MSB
+        };
+        e_ident[EI_VERSION] = EV_CURRENT;
+
+        Ok(match self.format.as_str() {
+            "ELF" if self.is_64 => Box::new(Elf64Ehdr {
+                e_ident,
+                e_machine: self.machine,
+                e_version: EV_CURRENT.into(),
+                e_entry: self.entry,
+                ..Default::default()
+            }),
+            "ELF" => Box::new(Elf32Ehdr {
+                e_ident,
+                e_machine: self.machine,
+                e_version: EV_CURRENT.into(),
+                e_entry: self
+                    .entry
+                    .try_into()
+                    .context("32-bit dump has an entry point above 4 GiB")?,
+                ..Default::default()
+            }),
+            "RAW" => Box::new(RawHeader {
+                machine: self.machine,
+                base_address: self.entry,
+            }),
+            other => bail!("Reloading a dump of a {} input is not supported", other),
+        })
+    }
+}
+
+impl BinaryAnalysis {
+    /// Rehydrate an analysis from a JSON file written by `dump-json`
+    ///
+    /// Functions are always restored. Sections (and therefore the raw bytes analyzers
+    /// need) and the header's machine, class and byte order are only available if the
+    /// dump was written with `--with-data`; otherwise analyzers return an error instead
+    /// of silently finding nothing. Only single-input JSON dumps of ELF files and raw
+    /// blobs can be reloaded.
+    pub fn from_json_dump<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.as_ref().display()))?;
+        let dump = match serde_json::from_str(&text) {
+            Ok(DumpFile::Single(dump)) => dump,
+            Ok(DumpFile::Inputs(inputs)) => bail!(
+                "Dump holds {} inputs ({}); reload a dump of a single input",
+                inputs.len(),
+                inputs.into_keys().collect::<Vec<_>>().join(", ")
+            ),
+            Err(_) if !text.trim_start().starts_with(['[', '{']) => bail!(
+                "{} is not a JSON dump; YAML, CSV and NDJSON dumps cannot be reloaded",
+                path.as_ref().display()
+            ),
+            Err(err) => return Err(err).context("parsing JSON dump"),
+        };
+
+        let (origin, header, functions, sections) = match dump {
+            AnalysisDump::Functions(functions) => (None, None, functions, Vec::new()),
+            AnalysisDump::Snapshot {
+                path,
+                header,
+                functions,
+                sections,
+            } => (Some(path), header, functions, sections),
+        };
+        let header = match header {
+            Some(header) => header.into_header()?,
+            None => Box::new(Elf64Ehdr::default()),
+        };
+
+        let sections = sections
+            .into_iter()
+            .map(|s| {
+                let raw = BASE64_STANDARD
+                    .decode(&s.data)
+                    .with_context(|| format!("decoding data of section {}", s.name))?;
+                Ok(KSection {
+                    name: s.name,
+                    vma: s.vma,
+                    size: s.size,
+                    file_offset: s.file_offset,
+                    flags: s.flags,
+                    raw_data: PlatformType::ELF(raw),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let function_sources: HashMap<u64, FunctionSource> = functions
+            .iter()
+            .filter_map(|f| Some((f.start, f.source.as_deref()?.parse().ok()?)))
+            .collect();
+
+        let mut functions: Vec<FunctionSignature> = functions
+            .into_iter()
+            .map(|f| FunctionSignature {
+                parent: f.parent,
+                visibility: f.visibility.and_then(|v| v.parse().ok()),
+                binding: f.binding.and_then(|b| b.parse().ok()),
+                is_ifunc: f.is_ifunc,
+                has_exception_handling: f.has_exception_handling,
+                // Older dumps have no score; fall back to the source's own
+                confidence: f.confidence.unwrap_or_else(|| {
+                    function_sources
+                        .get(&f.start)
+                        .map_or(0.0, |source| source.confidence())
+                }),
+                ..FunctionSignature::new(f.name, f.start, f.size)
+            })
+            .collect();
+        // Dumps may be edited by hand; lookups rely on address order
+        functions.sort_by_key(|f| f.start);
+
+        Ok(Self {
+            functions,
+            path: origin.unwrap_or_else(|| path.as_ref().display().to_string()),
+            is_stripped: sections.is_empty(),
+            section_headers: sections,
+            header,
+            options: Default::default(),
+            raw_buffer: Vec::new(),
+            function_sources,
+            call_graph: Vec::new(),
+            import_names: HashMap::new(),
+            boundary_conflicts: Vec::new(),
+            calling_conventions: HashMap::new(),
+            comments: HashMap::new(),
+            load_bias: 0,
+            providers: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(name: &str, json: &str) -> Result<BinaryAnalysis> {
+        let path =
+            std::env::temp_dir().join(format!("kakure-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, json).unwrap();
+        let loaded = BinaryAnalysis::from_json_dump(&path);
+        std::fs::remove_file(&path).unwrap();
+        loaded
+    }
+
+    #[test]
+    fn snapshot_header_is_rebuilt() {
+        let analysis = load(
+            "snapshot",
+            r#"{
+                "path": "t.elf",
+                "header": {"format": "ELF", "machine": 40, "is_64": false, "little_endian": false, "entry": 32768},
+                "functions": [{"name": "_start", "start": 32768, "size": 8}],
+                "sections": []
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(analysis.header.format_name(), "ELF");
+        assert_eq!(analysis.header.machine(), goblin::elf::header::EM_ARM);
+        assert!(!analysis.header.is_64());
+        assert!(!analysis.header.is_little_endian());
+        assert_eq!(analysis.header.entry_point(), 0x8000);
+    }
+
+    #[test]
+    fn multi_input_and_yaml_dumps_are_rejected() {
+        let functions = r#"[{"name": "f", "start": 4096, "size": 8}]"#;
+        let multi = format!(r#"{{"a.elf": {functions}, "b.elf": {functions}}}"#);
+        let err = load("multi", &multi).err().unwrap().to_string();
+        assert!(err.contains("2 inputs (a.elf, b.elf)"), "{err}");
+
+        let err = load("yaml", "- name: f\n  start: 4096\n  size: 8\n")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("YAML"), "{err}");
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+
+/// Container format to parse an input as, bypassing autodetection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// 32-bit ELF
+    Elf32,
+    /// 64-bit ELF
+    Elf64,
+    /// Mach-O (thin, single architecture)
+    MachO,
+    /// WebAssembly core module
+    Wasm,
+    /// Headerless machine code mapped at `base`, for an ELF `e_machine` value
+    Raw { base: u64, machine: u16 },
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Elf32 => write!(f, "elf32"),
+            Format::Elf64 => write!(f, "elf64"),
+            Format::MachO => write!(f, "macho"),
+            Format::Wasm => write!(f, "wasm"),
+            Format::Raw { base, machine } => write!(f, "raw@{:#x} (machine {})", base, machine),
+        }
+    }
+}
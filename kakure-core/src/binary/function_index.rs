@@ -0,0 +1,102 @@
+use crate::{BinaryAnalysis, FunctionSignature};
+
+/// Interval index over functions for "what intersects this address window" queries
+///
+/// Functions are sorted by start, with a running maximum of their ends. Since that
+/// maximum never decreases, a binary search finds the first function that can reach
+/// the window even when earlier functions overlap or nest inside each other.
+/// Borrowing the functions keeps it in step with them; rebuild it after analyzing more.
+#[derive(Debug, Clone)]
+pub struct FunctionIndex<'a> {
+    /// Sorted by start
+    functions: Vec<&'a FunctionSignature>,
+    /// `max_end[i]` is the furthest end among `functions[..=i]`
+    max_end: Vec<u64>,
+}
+
+impl<'a> FunctionIndex<'a> {
+    pub fn new(functions: &'a [FunctionSignature]) -> Self {
+        let mut functions: Vec<_> = functions.iter().collect();
+        functions.sort_by_key(|f| f.start);
+
+        let mut furthest = 0;
+        let max_end = functions
+            .iter()
+            .map(|f| {
+                furthest = furthest.max(extent_end(f));
+                furthest
+            })
+            .collect();
+        Self { functions, max_end }
+    }
+
+    /// Functions intersecting `[lo, hi)`, ordered by start
+    ///
+    /// A zero-size function counts as covering its start byte.
+    pub fn overlapping(&self, lo: u64, hi: u64) -> Vec<&'a FunctionSignature> {
+        if lo >= hi {
+            return Vec::new();
+        }
+        let first = self.max_end.partition_point(|&end| end <= lo);
+        let last = self.functions.partition_point(|f| f.start < hi);
+        self.functions
+            .get(first..last)
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|f| extent_end(f) > lo)
+            .collect()
+    }
+}
+
+/// End of the bytes `f` covers, counting a zero-size function's start byte
+fn extent_end(f: &FunctionSignature) -> u64 {
+    f.end.max(f.start.saturating_add(1))
+}
+
+impl BinaryAnalysis {
+    /// An interval index over the current functions; see [`FunctionIndex`]
+    pub fn function_index(&self) -> FunctionIndex<'_> {
+        FunctionIndex::new(&self.functions)
+    }
+
+    /// Functions intersecting `[lo, hi)`, ordered by start
+    ///
+    /// Builds a [`FunctionIndex`] on every call; hold one from `function_index`
+    /// for repeated queries.
+    pub fn functions_overlapping(&self, lo: u64, hi: u64) -> Vec<&FunctionSignature> {
+        self.function_index().overlapping(lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_and_overlapping_functions_are_found() {
+        let functions = [
+            FunctionSignature::new("outer", 0x1000, 0x100),
+            FunctionSignature::new("inner", 0x1010, 0x10),
+            FunctionSignature::new("after_inner", 0x1030, 0x10),
+            FunctionSignature::new("start_only", 0x1200, 0),
+            FunctionSignature::new("far", 0x2000, 0x10),
+        ];
+        let index = FunctionIndex::new(&functions);
+        let names = |lo, hi| -> Vec<&str> {
+            index
+                .overlapping(lo, hi)
+                .iter()
+                .map(|f| f.function_identifier.as_str())
+                .collect()
+        };
+
+        // `outer` reaches past both smaller functions that start before the window
+        assert_eq!(names(0x1080, 0x1090), ["outer"]);
+        assert_eq!(names(0x1015, 0x1035), ["outer", "inner", "after_inner"]);
+        assert_eq!(names(0x1100, 0x1200), Vec::<&str>::new());
+        assert_eq!(names(0x1200, 0x1201), ["start_only"]);
+        assert_eq!(names(0x1f00, 0x3000), ["far"]);
+        assert_eq!(names(0x1000, 0x1000), Vec::<&str>::new());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::BinaryAnalysis;
+use goblin::elf::note::{NT_GNU_ABI_TAG, NT_GNU_BUILD_ID};
+use goblin::elf::section_header::SHT_NOTE;
+
+/// One record from an ELF note section or segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfNote {
+    /// Owner of the note, e.g. "GNU"
+    pub name: String,
+    /// Meaning of `desc`, scoped to the owner (e.g. `NT_GNU_BUILD_ID`)
+    pub n_type: u32,
+    pub desc: Vec<u8>,
+}
+
+impl BinaryAnalysis {
+    /// Every note in the binary, in file order
+    ///
+    /// Read from the `SHT_NOTE` sections, or from the `PT_NOTE` segments when the
+    /// section headers are gone. A malformed note ends its section or segment with a
+    /// warning. Empty for anything but ELF.
+    pub fn notes(&self) -> Vec<ElfNote> {
+        if self.header.format_name() != "ELF" {
+            return Vec::new();
+        }
+        let Ok(elf) = goblin::elf::Elf::parse(&self.raw_buffer) else {
+            return Vec::new();
+        };
+
+        let has_note_sections = elf.section_headers.iter().any(|sh| sh.sh_type == SHT_NOTE);
+        let notes = if has_note_sections {
+            elf.iter_note_sections(&self.raw_buffer, None)
+        } else {
+            elf.iter_note_headers(&self.raw_buffer)
+        };
+
+        // goblin's note iterator doesn't advance past a malformed note, so the rest
+        // of that section or segment is given up on rather than retried forever
+        notes
+            .into_iter()
+            .flat_map(|notes| {
+                notes.map_while(|note| match note {
+                    Ok(note) => Some(ElfNote {
+                        name: note.name.trim_end_matches('\0').to_string(),
+                        n_type: note.n_type,
+                        desc: note.desc.to_vec(),
+                    }),
+                    Err(e) => {
+                        log::warn!("Skipping malformed notes: {}", e);
+                        None
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The linker's build ID from `.note.gnu.build-id` (`NT_GNU_BUILD_ID`), in lowercase hex
+    ///
+    /// Shared by a binary and its detached debug file, which is what debuggers and
+    /// symbol servers look the latter up by.
+    pub fn build_id(&self) -> Option<String> {
+        let note = self
+            .notes()
+            .into_iter()
+            .find(|note| note.name == "GNU" && note.n_type == NT_GNU_BUILD_ID)?;
+        (!note.desc.is_empty()).then(|| note.desc.iter().map(|b| format!("{b:02x}")).collect())
+    }
+
+    /// The OS and minimum kernel version from `.note.ABI-tag` (`NT_GNU_ABI_TAG`)
+    ///
+    /// e.g. `("Linux", (3, 2, 0))` for a glibc binary that needs Linux 3.2 or newer.
+    pub fn abi_tag(&self) -> Option<(String, (u32, u32, u32))> {
+        let note = self
+            .notes()
+            .into_iter()
+            .find(|note| note.name == "GNU" && note.n_type == NT_GNU_ABI_TAG)?;
+
+        let little_endian = self.header.is_little_endian();
+        let word = |i: usize| -> Option<u32> {
+            let bytes = note.desc.get(i * 4..i * 4 + 4)?.try_into().ok()?;
+            Some(if little_endian {
+                u32::from_le_bytes(bytes)
+            } else {
+                u32::from_be_bytes(bytes)
+            })
+        };
+
+        let os = match word(0)? {
+            0 => "Linux".to_string(),
+            1 => "GNU/Hurd".to_string(),
+            2 => "Solaris".to_string(),
+            3 => "FreeBSD".to_string(),
+            other => format!("unknown OS {}", other),
+        };
+        Some((os, (word(1)?, word(2)?, word(3)?)))
+    }
+}
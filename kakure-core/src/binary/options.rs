@@ -0,0 +1,71 @@
+use crate::{DemangleStyle, FunctionNaming};
+use std::path::PathBuf;
+
+/// Tunables that control how analyzers behave
+///
+/// Every field defaults to the crate's historical behavior, so
+/// `AnalysisOptions::default()` never changes results on well-formed binaries.
+#[derive(Debug, Clone)]
+pub struct AnalysisOptions {
+    /// Stop walking `.eh_frame` after this many CIE/FDE entries.
+    ///
+    /// Guards against adversarial unwind tables that would otherwise keep
+    /// the parser busy indefinitely.
+    pub max_eh_frame_entries: usize,
+
+    /// Read at most this many records from `.symtab` and from `.dynsym`.
+    ///
+    /// `None` reads them all. Debug builds of very large programs carry millions
+    /// of symbols; a cap keeps memory bounded at the cost of missing functions,
+    /// and is reported with a warning when it truncates a table.
+    pub max_symbols: Option<usize>,
+
+    /// How functions without a symbol name are labeled (`FUNC_0x...` by default).
+    pub naming: FunctionNaming,
+
+    /// Demangler used by `BinaryAnalysis::demangled_name`; raw names are never altered.
+    pub demangle: DemangleStyle,
+
+    /// Directories of `.dwo` files and `.dwp` packages for split-DWARF builds.
+    ///
+    /// Searched before the locations recorded in the binary itself.
+    pub split_dwarf: Vec<PathBuf>,
+
+    /// Treat disagreement between sources about where a function ends as an error.
+    ///
+    /// Conflicts are always recorded (see `BinaryAnalysis::boundary_conflicts`);
+    /// in strict mode `check_boundaries` and `analyze_all` fail on them.
+    pub strict: bool,
+
+    /// How many bytes two sources' end addresses may differ by before they conflict.
+    pub boundary_tolerance: u64,
+
+    /// Limit the prologue, call and jump-table scans to executable sections.
+    ///
+    /// Turn off for code sitting in a section without the executable flag (some
+    /// packers and hand-written linker scripts); every allocated section with file
+    /// bytes is scanned then, at the cost of false positives in data.
+    pub only_executable_sections: bool,
+
+    /// Drop functions smaller than this many bytes once analysis is finalized.
+    ///
+    /// One- to three-byte "functions" are usually padding or heuristic noise.
+    /// Functions of unknown (zero) size and manually added ones are always kept.
+    pub min_function_size: u64,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            max_eh_frame_entries: 1_000_000,
+            max_symbols: None,
+            naming: FunctionNaming::default(),
+            demangle: DemangleStyle::default(),
+            split_dwarf: Vec::new(),
+            strict: false,
+            boundary_tolerance: 0,
+            only_executable_sections: true,
+            min_function_size: 0,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::eh_frame::parse_eh_frame_personalities;
+use crate::BinaryAnalysis;
+use anyhow::{bail, Result};
+use std::collections::BTreeMap;
+
+/// A personality routine used for exception handling, from the CIEs in `.eh_frame`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalityRoutine {
+    /// Where the CIE points: the routine, or for an indirect pointer the slot that
+    /// holds its address (e.g. `DW.ref.__gxx_personality_v0`)
+    pub address: u64,
+    pub indirect: bool,
+    /// e.g. `__gxx_personality_v0` for C++ or `rust_eh_personality` for Rust
+    pub name: Option<String>,
+    /// How many functions' FDEs use it
+    pub functions: usize,
+}
+
+impl BinaryAnalysis {
+    /// The personality routines named in `.eh_frame`, ordered by address
+    ///
+    /// Names come from the dynamic relocation filling an indirect slot, then the
+    /// symbol tables, so imported routines resolve in stripped binaries too.
+    pub fn personality_routines(&self) -> Result<Vec<PersonalityRoutine>> {
+        self.require_section_data()?;
+        if self.get_section(".eh_frame").is_none() {
+            bail!(".eh_frame not found");
+        }
+
+        // Keyed like `parse_eh_frame_personalities`, merging counts across sections
+        let mut pointers: BTreeMap<(u64, bool), usize> = BTreeMap::new();
+        for section in self.sections_named(".eh_frame") {
+            for p in parse_eh_frame_personalities(
+                section.raw_data(),
+                section.vma,
+                self.dwarf_endian(),
+                self.options.max_eh_frame_entries,
+            )? {
+                *pointers.entry((p.address, p.indirect)).or_default() += p.functions;
+            }
+        }
+
+        Ok(pointers
+            .into_iter()
+            .map(|((address, indirect), functions)| PersonalityRoutine {
+                address,
+                indirect,
+                name: if indirect {
+                    self.slot_symbol(address)
+                } else {
+                    self.symbol_at(address)
+                },
+                functions,
+            })
+            .collect())
+    }
+
+    /// Name of the symbol whose value is `addr`, preferring recovered functions
+    fn symbol_at(&self, addr: u64) -> Option<String> {
+        if let Some(f) = self.functions.iter().find(|f| f.start == addr) {
+            return Some(f.function_identifier.clone());
+        }
+        let symbols = self.symbols().ok()?;
+        symbols
+            .entries
+            .iter()
+            .filter(|sym| sym.st_value == addr)
+            .map(|sym| sym.raw_name(symbols.strtab))
+            .find(|name| !name.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Name of whatever the pointer slot at `slot` refers to
+    fn slot_symbol(&self, slot: u64) -> Option<String> {
+        // Position-independent code leaves the slot to a dynamic relocation
+        if let Ok(elf) = goblin::elf::Elf::parse(&self.raw_buffer) {
+            let name = elf
+                .dynrelas
+                .iter()
+                .chain(elf.dynrels.iter())
+                .find(|reloc| reloc.r_offset == slot && reloc.r_sym != 0)
+                .and_then(|reloc| elf.dynsyms.get(reloc.r_sym))
+                .and_then(|sym| elf.dynstrtab.get_at(sym.st_name))
+                .filter(|name| !name.is_empty());
+            if let Some(name) = name {
+                return Some(name.to_string());
+            }
+        }
+
+        // GCC names the slot after the routine
+        if let Some(name) = self.symbol_at(slot) {
+            return Some(name.strip_prefix("DW.ref.").unwrap_or(&name).to_string());
+        }
+
+        // A statically linked slot holds the routine's address
+        self.symbol_at(self.read_pointer(slot)?)
+    }
+}
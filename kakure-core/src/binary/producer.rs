@@ -0,0 +1,48 @@
+use crate::BinaryAnalysis;
+use gimli::{Dwarf, EndianSlice, NativeEndian};
+
+impl BinaryAnalysis {
+    /// The toolchain that built the binary, e.g. `GCC: (GNU) 13.2.0`
+    ///
+    /// Taken from the `.comment` section (each distinct string, joined with `; `),
+    /// falling back to the first `DW_AT_producer` in `.debug_info`, and then in the
+    /// split debug info of a `-gsplit-dwarf` build (see [`BinaryAnalysis::split_units`]).
+    pub fn producer(&self) -> Option<String> {
+        self.comment_producer()
+            .or_else(|| root_producer(&self.dwarf()?))
+            .or_else(|| self.visit_split_dwarf(root_producer))
+    }
+
+    /// Distinct non-empty strings from `.comment`
+    fn comment_producer(&self) -> Option<String> {
+        let comment = self.get_section_data(".comment")?;
+        let mut strings: Vec<String> = Vec::new();
+        for s in comment.split(|&b| b == 0) {
+            let s = String::from_utf8_lossy(s).trim().to_string();
+            if !s.is_empty() && !strings.contains(&s) {
+                strings.push(s);
+            }
+        }
+        (!strings.is_empty()).then(|| strings.join("; "))
+    }
+}
+
+/// `DW_AT_producer` of the first compilation unit that has one
+fn root_producer(dwarf: &Dwarf<EndianSlice<'_, NativeEndian>>) -> Option<String> {
+    let mut headers = dwarf.units();
+    while let Ok(Some(header)) = headers.next() {
+        let Ok(unit) = dwarf.unit(header) else {
+            continue;
+        };
+        let mut entries = unit.entries();
+        let Ok(Some((_, root))) = entries.next_dfs() else {
+            continue;
+        };
+        if let Ok(Some(value)) = root.attr_value(gimli::DW_AT_producer) {
+            if let Ok(producer) = dwarf.attr_string(&unit, value) {
+                return Some(producer.to_string_lossy().into_owned());
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,120 @@
+use crate::{BinaryAnalysis, FunctionSignature, FunctionSource};
+use anyhow::Result;
+
+/// A pluggable source of functions, for heuristics that live outside kakure
+///
+/// Register one with [`BinaryAnalysis::add_provider`]; [`BinaryAnalysis::analyze_providers`]
+/// (also run by `analyze_all`) merges what it finds with the other sources.
+pub trait FunctionProvider: Send + Sync {
+    /// Short name used in logs, e.g. "vtable-scan"
+    fn name(&self) -> &str;
+
+    /// Find functions in `analysis`, which holds whatever earlier analyzers found
+    ///
+    /// Build each result with `FunctionSignature::new`; confidence is assigned from
+    /// `source_priority` when merging.
+    fn discover(&self, analysis: &BinaryAnalysis) -> Result<Vec<FunctionSignature>>;
+
+    /// The built-in source this provider's results rank as when sources disagree
+    fn source_priority(&self) -> FunctionSource {
+        FunctionSource::Heuristic
+    }
+}
+
+impl BinaryAnalysis {
+    /// Register a custom function source, run by `analyze_providers` in registration order
+    pub fn add_provider(&mut self, provider: Box<dyn FunctionProvider>) -> &mut Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// Run every registered provider and merge its functions by its `source_priority`
+    ///
+    /// A failing provider is an error; the functions of the providers before it
+    /// have already been merged.
+    pub fn analyze_providers(&mut self) -> Result<&mut Self> {
+        let providers = std::mem::take(&mut self.providers);
+        let mut result = Ok(());
+        for provider in &providers {
+            match provider.discover(self) {
+                Ok(functions) => {
+                    log::info!(
+                        "Found {} functions with {}",
+                        functions.len(),
+                        provider.name()
+                    );
+                    self.add_functions(functions, provider.source_priority());
+                }
+                Err(e) => {
+                    result = Err(e.context(format!("provider {} failed", provider.name())));
+                    break;
+                }
+            }
+        }
+        self.providers = providers;
+        result.map(|()| self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reports a fixed set of functions
+    struct Fixed(Vec<(&'static str, u64, u64)>, FunctionSource);
+
+    impl FunctionProvider for Fixed {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn discover(&self, _analysis: &BinaryAnalysis) -> Result<Vec<FunctionSignature>> {
+            Ok(self
+                .0
+                .iter()
+                .map(|&(name, start, size)| FunctionSignature::new(name, start, size))
+                .collect())
+        }
+
+        fn source_priority(&self) -> FunctionSource {
+            self.1
+        }
+    }
+
+    #[test]
+    fn provider_results_merge_by_their_priority() {
+        let bytes = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(bytes.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+
+        // Outranked by .symtab at 0x804805e, but the new start is kept
+        analysis.add_provider(Box::new(Fixed(
+            vec![("guess", 0x804805e, 6), ("hidden", 0x8048064, 2)],
+            FunctionSource::Heuristic,
+        )));
+        // Outranks .symtab
+        analysis.add_provider(Box::new(Fixed(
+            vec![("renamed_start", 0x8048054, 10)],
+            FunctionSource::Manual,
+        )));
+        analysis.analyze_providers().unwrap();
+
+        let functions: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.start))
+            .collect();
+        assert_eq!(
+            functions,
+            [
+                ("renamed_start", 0x8048054),
+                ("helper", 0x804805e),
+                ("hidden", 0x8048064)
+            ]
+        );
+        assert_eq!(
+            analysis.function_source(0x8048064),
+            Some(FunctionSource::Heuristic)
+        );
+    }
+}
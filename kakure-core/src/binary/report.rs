@@ -0,0 +1,304 @@
+use crate::call_graph::FunctionCallGraph;
+use crate::header::elf::{Elf32Ehdr, Elf64Ehdr};
+use crate::header::macho::MachHeader;
+use crate::header::raw::RawHeader;
+use crate::header::wasm::WasmHeader;
+use crate::header::Header;
+use crate::{BinaryAnalysis, FunctionSignature, KSection, PlatformType};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// Leading bytes of every report file
+const REPORT_MAGIC: &[u8; 4] = b"KKRP";
+
+/// Bumped whenever the layout of `Report` changes
+const REPORT_VERSION: u32 = 4;
+
+/// Enough of the header to rebuild it: the original header bytes plus what
+/// isn't stored in them (the Mach-O and WASM entry points, the raw blob's machine)
+#[derive(Serialize, Deserialize)]
+struct HeaderReport {
+    format: String,
+    is_64: bool,
+    machine: u16,
+    entry: u64,
+    bytes: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FunctionReport {
+    name: String,
+    start: u64,
+    end: u64,
+    size: u64,
+    visibility: Option<String>,
+    binding: Option<String>,
+    source: Option<String>,
+    is_ifunc: bool,
+    has_exception_handling: bool,
+    confidence: f32,
+    parent: Option<String>,
+}
+
+/// Section metadata; the bytes themselves are not stored
+#[derive(Serialize, Deserialize)]
+struct SectionReport {
+    name: String,
+    vma: u64,
+    size: u64,
+    file_offset: u64,
+    flags: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Report {
+    path: String,
+    is_stripped: bool,
+    header: HeaderReport,
+    functions: Vec<FunctionReport>,
+    sections: Vec<SectionReport>,
+    call_graph: Vec<(u64, u64)>,
+    import_names: Vec<(u64, String)>,
+}
+
+impl HeaderReport {
+    fn from_header(header: &dyn Header, raw_buffer: &[u8]) -> Self {
+        let len = match header.format_name() {
+            "ELF" if header.is_64() => 64,
+            "ELF" => 52,
+            "Mach-O" => 28,
+            "WASM" => 8,
+            _ => 0,
+        };
+        Self {
+            format: header.format_name().to_string(),
+            is_64: header.is_64(),
+            machine: header.machine(),
+            entry: header.entry_point(),
+            bytes: raw_buffer.get(..len).unwrap_or_default().to_vec(),
+        }
+    }
+
+    /// Rebuild the header; analyses that had no original bytes get a blank ELF header
+    fn into_header(self) -> Result<Box<dyn Header>> {
+        let mut cursor = std::io::Cursor::new(&self.bytes);
+        Ok(match self.format.as_str() {
+            _ if self.bytes.is_empty() && self.format != "RAW" => Box::new(Elf64Ehdr::default()),
+            "ELF" if self.is_64 => Box::new(Elf64Ehdr::from_reader(&mut cursor)?),
+            "ELF" => Box::new(Elf32Ehdr::from_reader(&mut cursor)?),
+            "Mach-O" => Box::new(MachHeader {
+                entry: self.entry,
+                ..MachHeader::from_reader(&mut cursor)?
+            }),
+            "WASM" => Box::new(WasmHeader {
+                entry: self.entry,
+                ..WasmHeader::from_reader(&mut cursor)?
+            }),
+            "RAW" => Box::new(RawHeader {
+                machine: self.machine,
+                base_address: self.entry,
+            }),
+            other => bail!("Report has an unknown header format '{}'", other),
+        })
+    }
+}
+
+impl BinaryAnalysis {
+    /// Write the analysis to `path` in kakure's compact binary report format
+    ///
+    /// The report holds the functions (with their sources), section metadata, the
+    /// header, the call graph and import names. Section bytes are left out, so a
+    /// reloaded report answers queries but cannot be analyzed further.
+    pub fn save_report<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let report = Report {
+            path: self.path.clone(),
+            is_stripped: self.is_stripped,
+            header: HeaderReport::from_header(self.header.as_ref(), &self.raw_buffer),
+            functions: self
+                .functions
+                .iter()
+                .map(|f| FunctionReport {
+                    name: f.function_identifier.clone(),
+                    start: f.start,
+                    end: f.end,
+                    size: f.size,
+                    visibility: f.visibility.map(|v| v.to_string()),
+                    binding: f.binding.map(|b| b.to_string()),
+                    source: self.function_source(f.start).map(|s| s.to_string()),
+                    is_ifunc: f.is_ifunc,
+                    has_exception_handling: f.has_exception_handling,
+                    confidence: f.confidence,
+                    parent: f.parent.clone(),
+                })
+                .collect(),
+            sections: self
+                .section_headers
+                .iter()
+                .map(|sh| SectionReport {
+                    name: sh.name.clone(),
+                    vma: sh.vma,
+                    size: sh.size,
+                    file_offset: sh.file_offset,
+                    flags: sh.flags,
+                })
+                .collect(),
+            call_graph: self
+                .call_graph
+                .iter()
+                .map(|edge| (edge.source_call, edge.jump_to))
+                .collect(),
+            import_names: self
+                .import_names
+                .iter()
+                .map(|(addr, name)| (*addr, name.clone()))
+                .collect(),
+        };
+
+        let mut file = std::fs::File::create(&path)
+            .with_context(|| format!("creating {}", path.as_ref().display()))?;
+        file.write_all(REPORT_MAGIC)?;
+        file.write_all(&REPORT_VERSION.to_le_bytes())?;
+        bincode::serialize_into(std::io::BufWriter::new(file), &report)
+            .context("writing report")?;
+        Ok(())
+    }
+
+    /// Reload an analysis written by [`BinaryAnalysis::save_report`]
+    pub fn load_report<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let mut file = std::fs::File::open(&path)
+            .with_context(|| format!("reading {}", path.as_ref().display()))?;
+        let mut preamble = [0u8; 8];
+        file.read_exact(&mut preamble)
+            .context("report is truncated")?;
+        if &preamble[..4] != REPORT_MAGIC {
+            bail!("{} is not a kakure report", path.as_ref().display());
+        }
+        let version = u32::from_le_bytes(preamble[4..].try_into().unwrap());
+        if version != REPORT_VERSION {
+            bail!(
+                "Report version {} is not supported (expected {})",
+                version,
+                REPORT_VERSION
+            );
+        }
+        let report: Report =
+            bincode::deserialize_from(std::io::BufReader::new(file)).context("parsing report")?;
+
+        let format = report.header.format.clone();
+        let header = report.header.into_header()?;
+        let sections = report
+            .sections
+            .into_iter()
+            .map(|s| KSection {
+                name: s.name,
+                vma: s.vma,
+                size: s.size,
+                file_offset: s.file_offset,
+                flags: s.flags,
+                raw_data: match format.as_str() {
+                    "ELF" => PlatformType::ELF(Vec::new()),
+                    "Mach-O" => PlatformType::MachO(Vec::new()),
+                    "WASM" => PlatformType::Wasm(Vec::new()),
+                    _ => PlatformType::Unknown(Vec::new()),
+                },
+            })
+            .collect();
+
+        let function_sources = report
+            .functions
+            .iter()
+            .filter_map(|f| Some((f.start, f.source.as_deref()?.parse().ok()?)))
+            .collect();
+        let functions = report
+            .functions
+            .into_iter()
+            .map(|f| FunctionSignature {
+                visibility: f.visibility.and_then(|v| v.parse().ok()),
+                binding: f.binding.and_then(|b| b.parse().ok()),
+                is_ifunc: f.is_ifunc,
+                has_exception_handling: f.has_exception_handling,
+                confidence: f.confidence,
+                parent: f.parent,
+                ..FunctionSignature::new(f.name, f.start, f.size)
+            })
+            .collect();
+
+        Ok(Self {
+            functions,
+            path: report.path,
+            section_headers: sections,
+            is_stripped: report.is_stripped,
+            header,
+            options: Default::default(),
+            raw_buffer: Vec::new(),
+            function_sources,
+            call_graph: report
+                .call_graph
+                .into_iter()
+                .map(|(source_call, jump_to)| FunctionCallGraph {
+                    source_call,
+                    jump_to,
+                })
+                .collect(),
+            import_names: report.import_names.into_iter().collect(),
+            boundary_conflicts: Vec::new(),
+            calling_conventions: HashMap::new(),
+            comments: HashMap::new(),
+            load_bias: 0,
+            providers: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_round_trips() {
+        let bytes = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/x86_32_symtab.elf"
+        ));
+        let mut analysis = BinaryAnalysis::from_bytes(bytes.to_vec()).unwrap();
+        analysis
+            .analyze_symtab()
+            .unwrap()
+            .identify_entry_point()
+            .add_manual_function("helper.cold", 0x8048070, 4)
+            .link_split_parts();
+
+        let path = std::env::temp_dir().join(format!("kakure-report-{}.kkr", std::process::id()));
+        analysis.save_report(&path).unwrap();
+        let loaded = BinaryAnalysis::load_report(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let summary = |a: &BinaryAnalysis| -> Vec<_> {
+            a.functions()
+                .iter()
+                .map(|f| {
+                    (
+                        f.function_identifier.clone(),
+                        f.start,
+                        f.end,
+                        a.function_source(f.start),
+                        f.confidence,
+                        f.parent.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(summary(&loaded), summary(&analysis));
+        assert!(loaded
+            .functions()
+            .iter()
+            .any(|f| f.parent.as_deref() == Some("helper")));
+        assert_eq!(loaded.section_headers.len(), analysis.section_headers.len());
+        assert_eq!(loaded.header.format_name(), "ELF");
+        assert!(!loaded.header.is_64());
+        assert_eq!(loaded.header.entry_point(), analysis.header.entry_point());
+        assert_eq!(loaded.header.machine(), analysis.header.machine());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::BinaryAnalysis;
+use gimli::{Dwarf, DwarfPackage, DwoId, EndianSlice, NativeEndian, SectionId};
+use goblin::elf::Elf;
+use std::path::{Path, PathBuf};
+
+type Slice<'a> = EndianSlice<'a, NativeEndian>;
+
+/// A skeleton compilation unit whose debug info lives in a separate `.dwo` file
+#[derive(Debug, Clone)]
+pub struct SplitUnit {
+    /// `DW_AT_dwo_name` (`DW_AT_GNU_dwo_name` before DWARF 5), relative to `comp_dir`
+    pub dwo_name: String,
+    /// Directory the compiler ran in
+    pub comp_dir: Option<String>,
+    /// Pairs the skeleton with its split unit inside a `.dwo` or `.dwp`
+    pub dwo_id: Option<u64>,
+    /// Where the `.dwo` was found, if anywhere
+    pub path: Option<PathBuf>,
+}
+
+/// Bytes of the named ELF section, or an empty slice
+fn elf_section<'a>(elf: &Elf, bytes: &'a [u8], name: Option<&str>) -> Slice<'a> {
+    let data = name
+        .and_then(|name| {
+            elf.section_headers
+                .iter()
+                .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(name))
+        })
+        .and_then(|sh| {
+            let end = sh.sh_offset.checked_add(sh.sh_size)?;
+            bytes.get(sh.sh_offset as usize..end as usize)
+        })
+        .unwrap_or(&[]);
+    EndianSlice::new(data, NativeEndian)
+}
+
+impl BinaryAnalysis {
+    /// The binary's own DWARF sections
+    pub(super) fn dwarf(&self) -> Option<Dwarf<Slice<'_>>> {
+        let load = |id: SectionId| -> gimli::Result<Slice<'_>> {
+            let data = self.get_section_data(id.name()).unwrap_or(&[]);
+            Ok(EndianSlice::new(data, NativeEndian))
+        };
+        Dwarf::load(load).ok()
+    }
+
+    /// Skeleton units of a `-gsplit-dwarf` build, with the `.dwo` file each one names
+    ///
+    /// A `.dwo` is looked for in every directory in `options.split_dwarf`, then at
+    /// `comp_dir/dwo_name`, then next to the binary.
+    pub fn split_units(&self) -> Vec<SplitUnit> {
+        let Some(dwarf) = self.dwarf() else {
+            return Vec::new();
+        };
+
+        let mut units = Vec::new();
+        let mut headers = dwarf.units();
+        while let Ok(Some(header)) = headers.next() {
+            let Ok(unit) = dwarf.unit(header) else {
+                continue;
+            };
+            let dwo_name = match unit.dwo_name() {
+                Ok(Some(value)) => dwarf
+                    .attr_string(&unit, value)
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+            if dwo_name.is_empty() && unit.dwo_id.is_none() {
+                continue;
+            }
+
+            let comp_dir = unit.comp_dir.map(|dir| dir.to_string_lossy().into_owned());
+            let path = self.find_dwo(&dwo_name, comp_dir.as_deref());
+            units.push(SplitUnit {
+                dwo_name,
+                comp_dir,
+                dwo_id: unit.dwo_id.map(|id| id.0),
+                path,
+            });
+        }
+        units
+    }
+
+    /// Locate the `.dwo` file named by a skeleton unit
+    fn find_dwo(&self, dwo_name: &str, comp_dir: Option<&str>) -> Option<PathBuf> {
+        if dwo_name.is_empty() {
+            return None;
+        }
+        let name = Path::new(dwo_name);
+        let binary_dir = Path::new(&self.path).parent();
+
+        let mut candidates = Vec::new();
+        for dir in self.options.split_dwarf.iter().filter(|p| p.is_dir()) {
+            candidates.push(dir.join(name));
+            if let Some(file_name) = name.file_name() {
+                candidates.push(dir.join(file_name));
+            }
+        }
+        candidates.extend(comp_dir.map(|dir| Path::new(dir).join(name)));
+        candidates.extend(binary_dir.map(|dir| dir.join(name)));
+
+        candidates.into_iter().find(|path| path.is_file())
+    }
+
+    /// `.dwp` packages to consult: files in `options.split_dwarf`, and `<binary>.dwp`
+    fn dwarf_packages(&self) -> Vec<PathBuf> {
+        let mut packages: Vec<PathBuf> = self
+            .options
+            .split_dwarf
+            .iter()
+            .filter(|p| p.is_file())
+            .cloned()
+            .collect();
+        let beside = PathBuf::from(format!("{}.dwp", self.path));
+        if beside.is_file() && !packages.contains(&beside) {
+            packages.push(beside);
+        }
+        packages
+    }
+
+    /// Run `visit` over the split debug info of each skeleton unit, returning the first hit
+    ///
+    /// Each `.dwo` that was found is tried, then every `.dwp` package. Files that fail
+    /// to read or parse are logged and skipped.
+    pub(super) fn visit_split_dwarf<T>(
+        &self,
+        mut visit: impl FnMut(&Dwarf<Slice<'_>>) -> Option<T>,
+    ) -> Option<T> {
+        let parent = self.dwarf()?;
+        let units = self.split_units();
+
+        for path in units.iter().filter_map(|unit| unit.path.as_ref()) {
+            let Some(bytes) = read_elf(path) else {
+                continue;
+            };
+            let Ok(elf) = Elf::parse(&bytes) else {
+                log::warn!("{} is not an ELF file", path.display());
+                continue;
+            };
+            let load = |id: SectionId| -> gimli::Result<Slice<'_>> {
+                Ok(elf_section(&elf, &bytes, id.dwo_name()))
+            };
+            let Ok(mut dwo) = Dwarf::load(load) else {
+                continue;
+            };
+            dwo.make_dwo(&parent);
+            if let Some(found) = visit(&dwo) {
+                return Some(found);
+            }
+        }
+
+        for path in self.dwarf_packages() {
+            let Some(bytes) = read_elf(&path) else {
+                continue;
+            };
+            let Ok(elf) = Elf::parse(&bytes) else {
+                log::warn!("{} is not an ELF file", path.display());
+                continue;
+            };
+            let load = |id: SectionId| -> gimli::Result<Slice<'_>> {
+                Ok(elf_section(&elf, &bytes, id.dwo_name()))
+            };
+            let Ok(package) = DwarfPackage::load(load, EndianSlice::new(&[], NativeEndian)) else {
+                log::warn!("{} is not a DWARF package", path.display());
+                continue;
+            };
+            for id in units.iter().filter_map(|unit| unit.dwo_id) {
+                if let Ok(Some(dwo)) = package.find_cu(DwoId(id), &parent) {
+                    if let Some(found) = visit(&dwo) {
+                        return Some(found);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Read a split-DWARF file, logging rather than failing
+fn read_elf(path: &Path) -> Option<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| log::warn!("Cannot read {}: {e}", path.display()))
+        .ok()
+}
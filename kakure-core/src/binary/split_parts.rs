@@ -0,0 +1,59 @@
+use crate::BinaryAnalysis;
+use std::collections::HashSet;
+
+impl BinaryAnalysis {
+    /// Set `parent` on the outlined pieces of optimized functions
+    ///
+    /// A function named like `foo.cold` or `foo.part.0` is linked to `foo` when a
+    /// function of that name was recovered; others are left alone. Run it after the
+    /// analyzers (and annotations), since it goes by name.
+    pub fn link_split_parts(&mut self) -> &mut Self {
+        let names: HashSet<String> = self
+            .functions
+            .iter()
+            .map(|f| f.function_identifier.clone())
+            .collect();
+
+        let mut linked = 0;
+        for f in &mut self.functions {
+            let parent = f.split_from().filter(|base| names.contains(*base));
+            f.parent = parent.map(str::to_string);
+            linked += usize::from(f.parent.is_some());
+        }
+        log::info!("Linked {} split function parts to their parents", linked);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cold_and_part_suffixes_link_to_existing_parents() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis
+            .add_manual_function("foo", 0x1000, 0x40)
+            .add_manual_function("foo.cold", 0x2000, 0x10)
+            .add_manual_function("foo.part.0", 0x2010, 0x10)
+            .add_manual_function("foo.part.0.cold.1", 0x2020, 0x10)
+            .add_manual_function("bar.cold", 0x2030, 0x10)
+            .add_manual_function("baz.3", 0x2040, 0x10)
+            .link_split_parts();
+
+        let parent = |name: &str| {
+            analysis
+                .functions
+                .iter()
+                .find(|f| f.function_identifier == name)
+                .and_then(|f| f.parent.as_deref())
+        };
+        assert_eq!(parent("foo.cold"), Some("foo"));
+        assert_eq!(parent("foo.part.0"), Some("foo"));
+        assert_eq!(parent("foo.part.0.cold.1"), Some("foo.part.0"));
+        assert_eq!(parent("bar.cold"), None);
+        assert_eq!(parent("baz.3"), None);
+        assert_eq!(parent("foo"), None);
+    }
+}
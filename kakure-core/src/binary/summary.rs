@@ -0,0 +1,78 @@
+use crate::{BinaryAnalysis, FunctionSource};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Overview of an analysis, for status lines and logs
+///
+/// Displays as one line of `key=value` pairs, e.g.
+/// `path=/bin/true format=ELF entry=0x4020 sections=29 functions=42 symtab=30 eh_frame=12`,
+/// with the function counts per source highest-priority first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisSummary {
+    pub path: String,
+    /// Container format, as `Header::format_name` reports it ("ELF", "Mach-O", ...)
+    pub format: &'static str,
+    pub entry_point: u64,
+    pub section_count: usize,
+    pub function_count: usize,
+    /// Functions per source that found them; sources with none are left out
+    pub by_source: BTreeMap<FunctionSource, usize>,
+}
+
+impl fmt::Display for AnalysisSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "path={} format={} entry={:#x} sections={} functions={}",
+            self.path, self.format, self.entry_point, self.section_count, self.function_count
+        )?;
+        for (source, count) in self.by_source.iter().rev() {
+            write!(f, " {source}={count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl BinaryAnalysis {
+    /// Function and section counts, entry point and format of the analysis so far
+    pub fn summary(&self) -> AnalysisSummary {
+        let mut by_source = BTreeMap::new();
+        for f in &self.functions {
+            if let Some(source) = self.function_source(f.start) {
+                *by_source.entry(source).or_default() += 1;
+            }
+        }
+        AnalysisSummary {
+            path: self.path.clone(),
+            format: self.header.format_name(),
+            entry_point: self.header.entry_point(),
+            section_count: self.sections().len(),
+            function_count: self.functions.len(),
+            by_source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_counts_functions_by_source() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+        analysis.add_manual_function("extra", 0x8048070, 4);
+
+        let summary = analysis.summary();
+        assert_eq!(summary.function_count, 3);
+        assert_eq!(
+            summary.by_source,
+            BTreeMap::from([(FunctionSource::SymTab, 2), (FunctionSource::Manual, 1)])
+        );
+        assert_eq!(
+            summary.to_string(),
+            "path=<memory> format=ELF entry=0x8048054 sections=6 functions=3 manual=1 symtab=2"
+        );
+    }
+}
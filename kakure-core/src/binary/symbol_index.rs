@@ -0,0 +1,116 @@
+use crate::BinaryAnalysis;
+use anyhow::Result;
+use goblin::elf::section_header::{SHN_ABS, SHN_COMMON};
+use goblin::elf::sym::{STT_FILE, STT_FUNC, STT_GNU_IFUNC, STT_OBJECT, STT_SECTION, STT_TLS};
+
+/// A named address from the symbol table, as held by a [`SymbolIndex`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexedSymbol<'a> {
+    pub name: &'a str,
+    pub address: u64,
+    /// `st_size`; 0 for labels and hand-written assembly symbols
+    pub size: u64,
+    /// `STT_*` type
+    pub sym_type: u8,
+}
+
+/// Address-sorted view of the symbol table for "which symbol is this address in" queries
+///
+/// Unlike [`BinaryAnalysis::symbols`], it keeps size-0 labels and drops what has no
+/// address: undefined, absolute, common, TLS, section and file symbols. Addresses
+/// follow any `rebase`. Borrowing the binary's string table keeps it cheap to
+/// build; hold one for repeated lookups.
+#[derive(Debug, Clone)]
+pub struct SymbolIndex<'a> {
+    /// Sorted by address; at equal addresses the preferred name comes last
+    symbols: Vec<IndexedSymbol<'a>>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    /// Every indexed symbol, in address order
+    pub fn symbols(&self) -> &[IndexedSymbol<'a>] {
+        &self.symbols
+    }
+
+    /// The closest symbol at or below `addr`, with `addr`'s offset from it
+    ///
+    /// e.g. `(main, 0x42)` for `main+0x42`. The offset may run past the symbol's
+    /// size; compare against `size` to tell a hit from the nearest label. Among
+    /// symbols at the same address, functions and objects win over untyped labels,
+    /// then global over local.
+    pub fn nearest_symbol_below(&self, addr: u64) -> Option<(&IndexedSymbol<'a>, u64)> {
+        let below = self.symbols.partition_point(|sym| sym.address <= addr);
+        let sym = self.symbols.get(below.checked_sub(1)?)?;
+        Some((sym, addr - sym.address))
+    }
+}
+
+impl BinaryAnalysis {
+    /// An address-sorted index over `.symtab` (or `.dynsym`); see [`SymbolIndex`]
+    pub fn symbol_index(&self) -> Result<SymbolIndex<'_>> {
+        let (_, data, strtab) = self.symbol_sections()?;
+        let mut symbols: Vec<_> = self
+            .read_all_symbols(data)?
+            .into_iter()
+            .filter(|sym| {
+                !sym.is_undefined()
+                    && ![SHN_ABS, SHN_COMMON].contains(&(sym.st_shndx as u32))
+                    && ![STT_TLS, STT_SECTION, STT_FILE].contains(&sym.sym_type())
+            })
+            .map(|sym| {
+                let typed = matches!(sym.sym_type(), STT_FUNC | STT_GNU_IFUNC | STT_OBJECT);
+                let global = !sym.binding().is_local();
+                let indexed = IndexedSymbol {
+                    name: sym.raw_name(strtab),
+                    address: self.slid_address(sym.st_value),
+                    size: sym.st_size,
+                    sym_type: sym.sym_type(),
+                };
+                ((typed, global), indexed)
+            })
+            .filter(|(_, sym)| !sym.name.is_empty())
+            .collect();
+        symbols.sort_by(|(a_rank, a), (b_rank, b)| {
+            (a.address, a_rank, a.name).cmp(&(b.address, b_rank, b.name))
+        });
+        Ok(SymbolIndex {
+            symbols: symbols.into_iter().map(|(_, sym)| sym).collect(),
+        })
+    }
+
+    /// The closest symbol at or below `addr`, with the offset from it
+    ///
+    /// Builds a [`SymbolIndex`] on every call; hold one from `symbol_index` for
+    /// repeated queries.
+    pub fn nearest_symbol_below(&self, addr: u64) -> Option<(String, u64)> {
+        let index = self.symbol_index().ok()?;
+        let (sym, offset) = index.nearest_symbol_below(addr)?;
+        Some((sym.name.to_string(), offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_resolve_to_the_closest_preceding_symbol() {
+        let elf = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        let index = analysis.symbol_index().unwrap();
+
+        let lookup = |addr| {
+            index
+                .nearest_symbol_below(addr)
+                .map(|(sym, offset)| (sym.name, offset))
+        };
+        assert_eq!(lookup(0x8048054), Some(("_start", 0)));
+        assert_eq!(lookup(0x8048060), Some(("helper", 2)));
+        assert_eq!(lookup(0x8048066), Some(("counter", 2)));
+        assert_eq!(lookup(0x8048000), None);
+        assert!(index
+            .symbols()
+            .windows(2)
+            .all(|pair| pair[0].address <= pair[1].address));
+    }
+}
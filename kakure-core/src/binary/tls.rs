@@ -0,0 +1,61 @@
+use crate::symtab::{parse_tls_symbols_64, DataSymbol};
+use crate::BinaryAnalysis;
+use goblin::elf::program_header::PT_TLS;
+
+/// The `PT_TLS` program header: where the TLS initialization image lives and how
+/// large each thread's block is
+#[derive(Debug, Clone, Copy)]
+pub struct TlsSegment {
+    /// Address of the initialization image (the start of `.tdata`)
+    pub vaddr: u64,
+    /// Bytes of initialized data (`.tdata`)
+    pub file_size: u64,
+    /// Size of each thread's block (`.tdata` plus `.tbss`)
+    pub mem_size: u64,
+    pub align: u64,
+}
+
+/// Thread-local storage layout of an ELF module
+#[derive(Debug, Clone, Default)]
+pub struct TlsLayout {
+    /// Size of `.tdata` (initialized thread-local data), 0 if absent
+    pub tdata_size: u64,
+    /// Size of `.tbss` (zero-initialized thread-local data), 0 if absent
+    pub tbss_size: u64,
+    /// The `PT_TLS` segment, if the binary has program headers
+    pub segment: Option<TlsSegment>,
+}
+
+impl BinaryAnalysis {
+    /// Thread-local (`STT_TLS`) symbols; `address` is the offset into the TLS block
+    pub fn tls_symbols(&self) -> anyhow::Result<Vec<DataSymbol>> {
+        let symbols = self.symbols()?;
+        Ok(parse_tls_symbols_64(&symbols.entries, symbols.strtab))
+    }
+
+    /// Sizes of `.tdata`/`.tbss` and the `PT_TLS` segment, or `None` without any TLS
+    pub fn tls_layout(&self) -> Option<TlsLayout> {
+        let section_size = |name: &str| self.get_section(name).map_or(0, |sh| sh.size);
+        let segment = goblin::elf::Elf::parse(&self.raw_buffer)
+            .ok()
+            .and_then(|elf| {
+                elf.program_headers
+                    .iter()
+                    .find(|ph| ph.p_type == PT_TLS)
+                    .map(|ph| TlsSegment {
+                        vaddr: ph.p_vaddr,
+                        file_size: ph.p_filesz,
+                        mem_size: ph.p_memsz,
+                        align: ph.p_align,
+                    })
+            });
+
+        let layout = TlsLayout {
+            tdata_size: section_size(".tdata"),
+            tbss_size: section_size(".tbss"),
+            segment,
+        };
+        (layout.tdata_size > 0 || layout.tbss_size > 0 || layout.segment.is_some())
+            .then_some(layout)
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+/// Which demangler to apply to symbol names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DemangleStyle {
+    /// Pick per name from its prefix: `_Z` (Itanium, or legacy Rust when it ends
+    /// in a hash), `_R` (Rust v0), `?` (MSVC)
+    #[default]
+    Auto,
+    /// Itanium C++ ABI (GCC, Clang)
+    GnuV3,
+    /// Rust, both legacy and v0 mangling
+    Rust,
+    /// MSVC C++. Recognized, but no MSVC demangler is bundled yet, so names are kept
+    Msvc,
+    /// Leave names as they are
+    None,
+}
+
+impl DemangleStyle {
+    /// Demangle `name`, or `None` if it isn't mangled in this style
+    pub fn demangle(self, name: &str) -> Option<String> {
+        match self {
+            DemangleStyle::None | DemangleStyle::Msvc => None,
+            DemangleStyle::GnuV3 => demangle_itanium(name),
+            DemangleStyle::Rust => demangle_rust(name),
+            DemangleStyle::Auto => {
+                // Mach-O prefixes every symbol with an extra underscore
+                let bare = name
+                    .strip_prefix('_')
+                    .filter(|n| n.starts_with("_Z") || n.starts_with("_R"));
+                let name = bare.unwrap_or(name);
+                if name.starts_with("_R") || (name.starts_with("_ZN") && has_rust_hash(name)) {
+                    demangle_rust(name)
+                } else if name.starts_with("_Z") {
+                    demangle_itanium(name)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Legacy Rust symbols end in a `17h<16 hex digits>E` hash segment
+fn has_rust_hash(name: &str) -> bool {
+    let Some(path) = name.strip_suffix('E') else {
+        return false;
+    };
+    path.len() >= 19
+        && path.is_char_boundary(path.len() - 19)
+        && path[path.len() - 19..].starts_with("17h")
+        && path[path.len() - 16..]
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit())
+}
+
+fn demangle_itanium(name: &str) -> Option<String> {
+    let symbol = cpp_demangle::Symbol::new(name).ok()?;
+    symbol
+        .demangle(&cpp_demangle::DemangleOptions::default())
+        .ok()
+}
+
+fn demangle_rust(name: &str) -> Option<String> {
+    // `{:#}` drops the trailing `::h<hash>`
+    rustc_demangle::try_demangle(name)
+        .ok()
+        .map(|demangled| format!("{:#}", demangled))
+}
+
+impl std::str::FromStr for DemangleStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(DemangleStyle::Auto),
+            "gnu-v3" | "itanium" => Ok(DemangleStyle::GnuV3),
+            "rust" => Ok(DemangleStyle::Rust),
+            "msvc" => Ok(DemangleStyle::Msvc),
+            "none" => Ok(DemangleStyle::None),
+            _ => Err(format!("Unknown demangle style: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for DemangleStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DemangleStyle::Auto => "auto",
+            DemangleStyle::GnuV3 => "gnu-v3",
+            DemangleStyle::Rust => "rust",
+            DemangleStyle::Msvc => "msvc",
+            DemangleStyle::None => "none",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_picks_the_demangler_per_name() {
+        let auto = DemangleStyle::Auto;
+        assert_eq!(
+            auto.demangle("_ZN3foo3barEv").as_deref(),
+            Some("foo::bar()")
+        );
+        assert_eq!(
+            auto.demangle("_ZN4core3fmt5write17h0123456789abcdefE")
+                .as_deref(),
+            Some("core::fmt::write")
+        );
+        assert_eq!(
+            auto.demangle("_RNvCs1234_7mycrate3foo").as_deref(),
+            Some("mycrate::foo")
+        );
+        assert_eq!(
+            auto.demangle("__ZN3foo3barEv").as_deref(),
+            Some("foo::bar()")
+        );
+        assert_eq!(auto.demangle("main"), None);
+        assert_eq!(DemangleStyle::None.demangle("_ZN3foo3barEv"), None);
+    }
+}
@@ -0,0 +1,57 @@
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Hash algorithms available for file and section digests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    /// CRC-32 (IEEE), as used by `.gnu_debuglink`
+    Crc32,
+    Md5,
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    pub const ALL: [DigestAlgorithm; 3] = [
+        DigestAlgorithm::Crc32,
+        DigestAlgorithm::Md5,
+        DigestAlgorithm::Sha256,
+    ];
+
+    /// Digest `data` and return it as a lowercase hex string
+    pub fn hex_digest(&self, data: &[u8]) -> String {
+        match self {
+            DigestAlgorithm::Crc32 => format!("{:08x}", crc32fast::hash(data)),
+            DigestAlgorithm::Md5 => to_hex(&Md5::digest(data)),
+            DigestAlgorithm::Sha256 => to_hex(&Sha256::digest(data)),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl std::str::FromStr for DigestAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "crc32" => Ok(DigestAlgorithm::Crc32),
+            "md5" => Ok(DigestAlgorithm::Md5),
+            "sha256" | "sha-256" => Ok(DigestAlgorithm::Sha256),
+            _ => Err(format!("Unknown digest algorithm: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DigestAlgorithm::Crc32 => "crc32",
+            DigestAlgorithm::Md5 => "md5",
+            DigestAlgorithm::Sha256 => "sha256",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -1,12 +1,280 @@
-mod call_graph;
+pub mod call_graph;
+pub mod control_flow;
 pub mod frame_analyzers;
 
 pub use frame_analyzers::*;
 
+use crate::symtab::{SymbolBinding, SymbolVisibility};
+use crate::BinaryAnalysis;
+
 #[derive(Debug, Clone)]
 pub struct FunctionSignature {
     pub function_identifier: String,
     pub start: u64,
     pub end: u64,
     pub size: u64,
+    /// Symbol visibility, when the function came from a symbol table
+    pub visibility: Option<SymbolVisibility>,
+    /// Symbol binding (local/static vs. exported), when the function came from a symbol table
+    pub binding: Option<SymbolBinding>,
+    /// GNU indirect function (`STT_GNU_IFUNC`): `start` is the resolver that picks
+    /// the real implementation at load time, not the implementation itself
+    pub is_ifunc: bool,
+    /// The FDE covering this function points at an LSDA (language-specific data area),
+    /// i.e. it has landing pads such as C++ `catch` blocks or destructor cleanups
+    pub has_exception_handling: bool,
+    /// How far to trust these boundaries, from 0.0 to 1.0
+    ///
+    /// Filled in when the function is added to a `BinaryAnalysis`: the base score of
+    /// its `FunctionSource`, raised when another source reports the same start and end.
+    pub confidence: f32,
+    /// The function this one was split out of (`foo` for `foo.cold` or `foo.part.0`)
+    ///
+    /// Only set by `BinaryAnalysis::link_split_parts`.
+    pub parent: Option<String>,
+    /// Index of the ELF section holding the function, from its symbol's `st_shndx`
+    ///
+    /// Needed to find the bytes of relocatable objects, where every section starts
+    /// at address 0.
+    pub section: Option<usize>,
+}
+
+impl FunctionSignature {
+    /// A function of `size` bytes at `start`, with `end` derived from them
+    ///
+    /// The one place `end` is computed, so `end == start + size` holds for every
+    /// analyzer. A size running past the end of the address space (a corrupt symbol
+    /// or FDE) is dropped with a warning, leaving a zero-sized function at `start`.
+    /// Symbol metadata is unset and `confidence` is filled in when the function is
+    /// added to a `BinaryAnalysis`; use struct update syntax to set the rest.
+    pub fn new(function_identifier: impl Into<String>, start: u64, size: u64) -> Self {
+        let function_identifier = function_identifier.into();
+        let (end, size) = match start.checked_add(size) {
+            Some(end) => (end, size),
+            None => {
+                log::warn!(
+                    "{} at {:#x} claims {:#x} bytes, past the end of the address space; treating it as zero-sized",
+                    function_identifier,
+                    start,
+                    size
+                );
+                (start, 0)
+            }
+        };
+        Self {
+            function_identifier,
+            start,
+            end,
+            size,
+            visibility: None,
+            binding: None,
+            is_ifunc: false,
+            has_exception_handling: false,
+            confidence: 0.0,
+            parent: None,
+            section: None,
+        }
+    }
+
+    /// Stable identifier for this function, usable as a map key across analysis passes
+    ///
+    /// Derived from the start address, which is what deduplication keys on, so it
+    /// survives renames (e.g. `FUNC_0x1139` becoming `helper` once symbols load) and
+    /// stays unique where names collide or are empty.
+    pub fn id(&self) -> u64 {
+        self.start
+    }
+
+    /// Whether `addr` falls inside the function: `start <= addr < end`
+    ///
+    /// A zero-size function (a start with no known extent) contains only its start.
+    pub fn contains(&self, addr: u64) -> bool {
+        self.start <= addr && (addr < self.end || addr == self.start)
+    }
+
+    /// How far into the function `addr` is, or `None` if the function doesn't contain it
+    pub fn offset_of(&self, addr: u64) -> Option<u64> {
+        self.contains(addr).then(|| addr - self.start)
+    }
+
+    /// The machine code of this function, `[start, end)`, from the section holding it
+    ///
+    /// Shorthand for [`BinaryAnalysis::function_bytes`].
+    pub fn bytes<'a>(&self, analysis: &'a BinaryAnalysis) -> Option<&'a [u8]> {
+        analysis.function_bytes(self)
+    }
+
+    /// The name this one was split from, if it ends in a compiler split suffix
+    ///
+    /// GCC and Clang outline cold paths as `foo.cold` / `foo.cold.1` and partially
+    /// inlined bodies as `foo.part.0`. Only the last suffix is removed, so
+    /// `foo.part.0.cold` yields `foo.part.0`.
+    pub fn split_from(&self) -> Option<&str> {
+        let name = self.function_identifier.as_str();
+        // An optional numeric suffix, e.g. the `.1` of `foo.cold.1`
+        let unnumbered = match name.rsplit_once('.') {
+            Some((rest, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => {
+                (rest, true)
+            }
+            _ => (name, false),
+        };
+        let base = match unnumbered {
+            (rest, _) if rest.ends_with(".cold") => rest.strip_suffix(".cold"),
+            (rest, true) => rest.strip_suffix(".part"),
+            _ => None,
+        }?;
+        (!base.is_empty()).then_some(base)
+    }
+}
+
+/// How functions without a symbol name are labeled
+///
+/// The default yields `FUNC_0x401000`; [`FunctionNaming::ida`] yields `sub_401000`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionNaming {
+    /// Text placed before the address
+    pub prefix: String,
+    /// Write the address as `0x`-prefixed lowercase hex rather than bare uppercase hex
+    pub hex_prefix: bool,
+}
+
+impl FunctionNaming {
+    /// IDA-style `sub_401000` names
+    pub fn ida() -> Self {
+        Self {
+            prefix: "sub_".to_string(),
+            hex_prefix: false,
+        }
+    }
+
+    /// Name for an anonymous function starting at `addr`
+    pub fn name(&self, addr: u64) -> String {
+        if self.hex_prefix {
+            format!("{}{:#x}", self.prefix, addr)
+        } else {
+            format!("{}{:X}", self.prefix, addr)
+        }
+    }
+
+    /// Whether `name` is one this scheme generated: the prefix followed by an
+    /// address written exactly as [`FunctionNaming::name`] writes it
+    pub fn is_generated(&self, name: &str) -> bool {
+        let Some(addr) = name.strip_prefix(&self.prefix) else {
+            return false;
+        };
+        let digits = if self.hex_prefix {
+            addr.strip_prefix("0x")
+        } else {
+            Some(addr)
+        };
+        digits
+            .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+            .is_some_and(|addr| self.name(addr) == name)
+    }
+}
+
+impl Default for FunctionNaming {
+    fn default() -> Self {
+        Self {
+            prefix: "FUNC_".to_string(),
+            hex_prefix: true,
+        }
+    }
+}
+
+impl std::str::FromStr for FunctionNaming {
+    type Err = String;
+
+    /// `func` (the default), `ida`, or any other text to use as a custom prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("Naming prefix cannot be empty".to_string()),
+            "func" => Ok(Self::default()),
+            "ida" => Ok(Self::ida()),
+            prefix => Ok(Self {
+                prefix: prefix.to_string(),
+                hex_prefix: true,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn containment_excludes_the_end() {
+        let mut f = FunctionSignature::new("f", 0x1000, 0x10);
+        assert!(f.contains(0x1000) && f.contains(0x100f));
+        assert!(!f.contains(0xfff) && !f.contains(0x1010));
+        assert_eq!(f.offset_of(0x100f), Some(0xf));
+        assert_eq!(f.offset_of(0x1010), None);
+
+        // A start with no known extent still contains itself
+        f = FunctionSignature::new("f", 0x1000, 0);
+        assert_eq!(f.offset_of(0x1000), Some(0));
+        assert!(!f.contains(0x1001));
+    }
+
+    #[test]
+    fn generated_names_need_an_address_after_the_prefix() {
+        let ida = FunctionNaming::ida();
+        assert!(ida.is_generated("sub_401000"));
+        assert!(!ida.is_generated("sub_matrix"));
+        assert!(!ida.is_generated("sub_"));
+        assert!(!ida.is_generated("sub_401000a"));
+        // IDA writes uppercase digits without a `0x`
+        assert!(!ida.is_generated("sub_40100a"));
+        assert!(!ida.is_generated("sub_0x401000"));
+
+        let default = FunctionNaming::default();
+        assert!(default.is_generated("FUNC_0x401000"));
+        assert!(!default.is_generated("FUNC_401000"));
+        assert!(!default.is_generated("FUNC_0xtable"));
+    }
+
+    #[test]
+    fn bytes_slice_the_containing_section() {
+        let elf = include_bytes!("../tests/fixtures/x86_32_symtab.elf");
+        let mut analysis = BinaryAnalysis::from_bytes(elf.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+
+        let helper = analysis
+            .functions
+            .iter()
+            .find(|f| f.function_identifier == "helper")
+            .unwrap();
+        // mov eax, 1; ret
+        assert_eq!(
+            helper.bytes(&analysis),
+            Some(&[0xb8, 0x01, 0x00, 0x00, 0x00, 0xc3][..])
+        );
+        assert_eq!(
+            FunctionSignature::new("nowhere", 0x10, 4).bytes(&analysis),
+            None
+        );
+    }
+
+    #[test]
+    fn relocatable_bytes_come_from_the_symbols_section() {
+        // `first` at .text+4, `second` at .text.second+8; both sections start at 0
+        let object = include_bytes!("../tests/fixtures/x86_64_rel.o");
+        let mut analysis = BinaryAnalysis::from_bytes(object.to_vec()).unwrap();
+        analysis.analyze_symtab().unwrap();
+
+        let bytes: Vec<_> = analysis
+            .functions
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.bytes(&analysis)))
+            .collect();
+        assert_eq!(
+            bytes,
+            [
+                ("first", Some(&[0xb8, 0x01, 0x00, 0x00, 0x00, 0xc3][..])),
+                ("second", Some(&[0xb8, 0x02, 0x00, 0x00, 0x00, 0xc3][..])),
+            ]
+        );
+        assert!(analysis.group_identical_functions().is_empty());
+    }
 }
@@ -1,8 +1,323 @@
-enum CallFormat {
-    FastCall,
+use crate::{Arch, ArchSupport};
+use iced_x86::{Instruction, MemorySize, Mnemonic, OpKind, Register};
+
+/// Calling convention of a function, as far as it can be told from the binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallFormat {
+    /// x86-32 `__cdecl`: arguments on the stack, caller cleans up
+    Cdecl,
+    /// x86-32 `__stdcall`: arguments on the stack, callee pops them (`ret imm16`)
     StdCall,
+    /// x86-32 `__fastcall`: first two arguments in `ecx`/`edx`
+    FastCall,
+    /// System V AMD64 ABI (Linux, BSD, macOS)
+    SysV64,
+    /// AArch64 Procedure Call Standard
+    Aapcs64,
+    /// 32-bit ARM Procedure Call Standard
+    Aapcs,
+}
+
+impl CallFormat {
+    /// Best-effort convention for a function on `arch`
+    ///
+    /// The platform ABI decides everywhere except x86-32, where a `ret imm16` in
+    /// `code` marks a callee-cleanup (`stdcall`) function and anything else is taken
+    /// to be `cdecl`. `fastcall` cannot be told apart from `cdecl` this way.
+    pub fn infer(arch: Arch, code: Option<(&[u8], u64)>) -> Option<Self> {
+        match arch {
+            Arch::X86_64 => Some(CallFormat::SysV64),
+            Arch::Aarch64 => Some(CallFormat::Aapcs64),
+            Arch::Arm => Some(CallFormat::Aapcs),
+            Arch::X86 => {
+                let pops_arguments = code.is_some_and(|(data, base)| {
+                    let Some(mut decoder) = arch.support().decoder(data, base) else {
+                        return false;
+                    };
+                    decoder.iter().any(|insn| {
+                        insn.mnemonic() == Mnemonic::Ret
+                            && insn.op_count() == 1
+                            && insn.immediate16() > 0
+                    })
+                });
+                Some(if pops_arguments {
+                    CallFormat::StdCall
+                } else {
+                    CallFormat::Cdecl
+                })
+            }
+            Arch::Other(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CallFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CallFormat::Cdecl => "cdecl",
+            CallFormat::StdCall => "stdcall",
+            CallFormat::FastCall => "fastcall",
+            CallFormat::SysV64 => "sysv64",
+            CallFormat::Aapcs64 => "aapcs64",
+            CallFormat::Aapcs => "aapcs",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A call edge: the instruction at `source_call` transfers control to `jump_to`
+///
+/// For `call [rip+disp]` style calls `jump_to` is the address of the memory slot
+/// (usually a GOT entry) rather than the code it eventually reaches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionCallGraph {
+    pub source_call: u64, // Address where the call originates from
+    pub jump_to: u64,
+}
+
+/// Address of a memory operand that doesn't depend on register state (`[rip+disp]` or `[disp]`)
+fn static_memory_slot(insn: &Instruction) -> Option<u64> {
+    if insn.is_ip_rel_memory_operand() {
+        Some(insn.ip_rel_memory_address())
+    } else if insn.memory_base() == Register::None && insn.memory_index() == Register::None {
+        Some(insn.memory_displacement64())
+    } else {
+        None
+    }
+}
+
+/// Linear-sweep `data` (mapped at `base_address`) for calls with a statically known target
+///
+/// Register-indirect calls are skipped. Returns an empty list (with a warning) for
+/// architectures the decoder doesn't cover.
+pub fn scan_calls(
+    data: &[u8],
+    base_address: u64,
+    arch: &dyn ArchSupport,
+) -> Vec<FunctionCallGraph> {
+    let Some(mut decoder) = arch.decoder(data, base_address) else {
+        log::warn!("No call decoder for this architecture");
+        return Vec::new();
+    };
+
+    let mut edges = Vec::new();
+    let mut insn = Instruction::default();
+    while decoder.can_decode() {
+        decoder.decode_out(&mut insn);
+        if !arch.is_call(&insn) {
+            continue;
+        }
+
+        let target = match insn.op0_kind() {
+            OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64 => {
+                Some(insn.near_branch_target())
+            }
+            OpKind::Memory => static_memory_slot(&insn),
+            _ => None,
+        };
+        if let Some(jump_to) = target {
+            edges.push(FunctionCallGraph {
+                source_call: insn.ip(),
+                jump_to,
+            });
+        }
+    }
+
+    edges
+}
+
+/// Find PLT stubs in `data` and the GOT slot each one jumps through, as `(stub, slot)` pairs
+///
+/// A stub starts at its `jmp [slot]`, or at the `endbr64`/`endbr32` right before it
+/// on IBT-enabled binaries.
+pub fn scan_plt_stubs(data: &[u8], base_address: u64, arch: &dyn ArchSupport) -> Vec<(u64, u64)> {
+    let Some(mut decoder) = arch.decoder(data, base_address) else {
+        return Vec::new();
+    };
+
+    let mut stubs = Vec::new();
+    let mut prev = Instruction::default();
+    let mut insn = Instruction::default();
+    while decoder.can_decode() {
+        decoder.decode_out(&mut insn);
+        if insn.mnemonic() == Mnemonic::Jmp && insn.op0_kind() == OpKind::Memory {
+            if let Some(slot) = static_memory_slot(&insn) {
+                let is_landing_pad =
+                    matches!(prev.mnemonic(), Mnemonic::Endbr64 | Mnemonic::Endbr32)
+                        && prev.next_ip() == insn.ip();
+                let stub = if is_landing_pad { prev.ip() } else { insn.ip() };
+                stubs.push((stub, slot));
+            }
+        }
+        prev = insn;
+    }
+
+    stubs
+}
+
+/// How the entries of a jump table encode their targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpTableKind {
+    /// Absolute addresses of `size` bytes, as used by `jmp [table + reg*8]`
+    Absolute { size: usize },
+    /// Signed 32-bit offsets from the table start, as emitted for position-independent code:
+    /// `lea base, [rip+table]; movsxd r, [base + idx*4]; add r, base; jmp r`
+    Relative,
+}
+
+/// An indirect jump through a table whose address is known statically
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JumpTable {
+    /// Address of the `jmp` instruction
+    pub jump: u64,
+    /// Address of the first table entry
+    pub table: u64,
+    pub kind: JumpTableKind,
+}
+
+/// Linear-sweep `data` (mapped at `base_address`) for jumps through switch tables
+///
+/// Only the two shapes compilers emit for switches are recognized (see
+/// [`JumpTableKind`]); any other register-indirect jump is skipped.
+pub fn scan_jump_tables(data: &[u8], base_address: u64, arch: &dyn ArchSupport) -> Vec<JumpTable> {
+    let Some(mut decoder) = arch.decoder(data, base_address) else {
+        return Vec::new();
+    };
+
+    let mut tables = Vec::new();
+    // The instructions before the current one, oldest first
+    let mut window = [Instruction::default(); WINDOW];
+    let mut insn = Instruction::default();
+    while decoder.can_decode() {
+        decoder.decode_out(&mut insn);
+        if insn.mnemonic() == Mnemonic::Jmp {
+            let kind = match insn.op0_kind() {
+                OpKind::Memory => absolute_table(&insn),
+                OpKind::Register => relative_table(&window, insn.op0_register()),
+                _ => None,
+            };
+            if let Some((table, kind)) = kind {
+                tables.push(JumpTable {
+                    jump: insn.ip(),
+                    table,
+                    kind,
+                });
+            }
+        }
+        window.rotate_left(1);
+        window[WINDOW - 1] = insn;
+    }
+
+    tables
+}
+
+/// `jmp [table + idx*size]` with no base register, where `size` is the pointer width
+fn absolute_table(jmp: &Instruction) -> Option<(u64, JumpTableKind)> {
+    let size = match jmp.memory_size() {
+        MemorySize::DwordOffset => 4,
+        MemorySize::QwordOffset => 8,
+        _ => return None,
+    };
+    let indexed = jmp.memory_base() == Register::None && jmp.memory_index() != Register::None;
+    (indexed && jmp.memory_index_scale() as usize == size).then(|| {
+        (
+            jmp.memory_displacement64(),
+            JumpTableKind::Absolute { size },
+        )
+    })
+}
+
+/// Instructions kept to match the position-independent table sequence; compilers
+/// sometimes schedule an unrelated instruction (e.g. index zero-extension) after the `lea`
+const WINDOW: usize = 5;
+
+/// The `lea`/`movsxd`/`add` sequence ending in `jmp target`
+fn relative_table(
+    window: &[Instruction; WINDOW],
+    target: Register,
+) -> Option<(u64, JumpTableKind)> {
+    let [earlier @ .., load, add] = window;
+    let base = load.memory_base();
+    let loads_offset = load.mnemonic() == Mnemonic::Movsxd
+        && load.op0_register() == target
+        && load.op1_kind() == OpKind::Memory
+        && load.memory_index_scale() == 4
+        && add.mnemonic() == Mnemonic::Add
+        && add.op0_register() == target
+        && add.op1_register() == base;
+    if !loads_offset {
+        return None;
+    }
+
+    let lea = earlier.iter().rev().find(|insn| {
+        insn.mnemonic() == Mnemonic::Lea
+            && insn.op0_register() == base
+            && insn.is_ip_rel_memory_operand()
+    })?;
+    Some((lea.ip_rel_memory_address(), JumpTableKind::Relative))
+}
+
+/// Decode the targets of `table`, whose entries start at `data`
+///
+/// Tables carry no length, so entries are read until one doesn't satisfy
+/// `is_code` (or `data` runs out). Callers should end `data` at the next
+/// known table so adjacent tables aren't read as one.
+pub fn jump_table_targets(
+    data: &[u8],
+    table: &JumpTable,
+    is_code: impl Fn(u64) -> bool,
+) -> Vec<u64> {
+    let size = match table.kind {
+        JumpTableKind::Absolute { size } => size,
+        JumpTableKind::Relative => 4,
+    };
+    data.chunks_exact(size)
+        .map(|entry| match table.kind {
+            JumpTableKind::Relative => table
+                .table
+                .wrapping_add_signed(i32::from_le_bytes(entry.try_into().unwrap()).into()),
+            JumpTableKind::Absolute { size: 4 } => {
+                u32::from_le_bytes(entry.try_into().unwrap()).into()
+            }
+            JumpTableKind::Absolute { .. } => u64::from_le_bytes(entry.try_into().unwrap()),
+        })
+        .take_while(|&target| is_code(target))
+        .collect()
 }
-struct FunctionCallGraph {
-    source_call: u64, // Address where the call originates from
-    jump_to: u64,
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Arch;
+
+    #[test]
+    fn switch_tables_are_recognized_and_read() {
+        let arch = Arch::X86_64.support();
+        // jmp qword [rax*8 + 0x402000]
+        let absolute = [0xff, 0x24, 0xc5, 0x00, 0x20, 0x40, 0x00];
+        // lea rdx, [rip+0x10]; movsxd rax, dword [rdx+rax*4]; add rax, rdx; jmp rax
+        let relative = [
+            0x48, 0x8d, 0x15, 0x10, 0x00, 0x00, 0x00, 0x48, 0x63, 0x04, 0x82, 0x48, 0x01, 0xd0,
+            0xff, 0xe0,
+        ];
+
+        let [abs] = scan_jump_tables(&absolute, 0x401000, arch)[..] else {
+            panic!("absolute table not found");
+        };
+        assert_eq!(abs.table, 0x402000);
+        assert_eq!(abs.kind, JumpTableKind::Absolute { size: 8 });
+
+        let [rel] = scan_jump_tables(&relative, 0x1000, arch)[..] else {
+            panic!("relative table not found");
+        };
+        assert_eq!((rel.jump, rel.table), (0x100e, 0x1017));
+        assert_eq!(rel.kind, JumpTableKind::Relative);
+
+        // Two offsets back into the code, then data that points nowhere
+        let entries = [
+            0xe9, 0xff, 0xff, 0xff, 0xf0, 0xff, 0xff, 0xff, 0, 0, 0, 0x40,
+        ];
+        let targets = jump_table_targets(&entries, &rel, |addr| (0x1000..0x1010).contains(&addr));
+        assert_eq!(targets, [0x1000, 0x1007]);
+    }
 }
@@ -0,0 +1,169 @@
+use crate::ArchSupport;
+use iced_x86::{FlowControl, Instruction};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A straight-line run of instructions, entered only at `start`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicBlock {
+    pub start: u64,
+    /// One past the last instruction
+    pub end: u64,
+    /// Starts of the blocks control can continue in, within the same function
+    pub successors: Vec<u64>,
+}
+
+/// Where control goes after `insn` if it ends a block: `(branch targets, falls through)`
+fn exits(
+    insn: &Instruction,
+    indirect_targets: &HashMap<u64, Vec<u64>>,
+) -> Option<(Vec<u64>, bool)> {
+    match insn.flow_control() {
+        FlowControl::ConditionalBranch => Some((vec![insn.near_branch_target()], true)),
+        FlowControl::UnconditionalBranch => Some((vec![insn.near_branch_target()], false)),
+        FlowControl::IndirectBranch => Some((
+            indirect_targets
+                .get(&insn.ip())
+                .cloned()
+                .unwrap_or_default(),
+            false,
+        )),
+        FlowControl::Return | FlowControl::Exception => Some((Vec::new(), false)),
+        _ => None,
+    }
+}
+
+/// Split the function `data` (mapped at `start`) into the basic blocks reachable from its entry
+///
+/// Calls are assumed to return. Branches leaving the function (tail calls) and
+/// returns end a block without successors, as do indirect jumps unless
+/// `indirect_targets` lists targets for them (e.g. switch tables, keyed by the
+/// `jmp` address). Blocks come back sorted by address. `None` for architectures
+/// the decoder doesn't cover.
+pub fn basic_blocks(
+    data: &[u8],
+    start: u64,
+    arch: &dyn ArchSupport,
+    indirect_targets: &HashMap<u64, Vec<u64>>,
+) -> Option<Vec<BasicBlock>> {
+    let mut decoder = arch.decoder(data, start)?;
+    let instructions: Vec<Instruction> = decoder.iter().collect();
+    let boundaries: HashSet<u64> = instructions.iter().map(Instruction::ip).collect();
+    // Branches into the middle of an instruction are misdecodes; ignore them
+    let lands = |addr: &u64| boundaries.contains(addr);
+
+    let mut leaders = BTreeSet::from([start]);
+    for insn in &instructions {
+        if let Some((targets, _)) = exits(insn, indirect_targets) {
+            leaders.extend(targets.into_iter().filter(lands));
+            leaders.insert(insn.next_ip());
+        }
+    }
+
+    let mut blocks: HashMap<u64, BasicBlock> = HashMap::new();
+    let mut block_start = start;
+    for (i, insn) in instructions.iter().enumerate() {
+        if leaders.contains(&insn.ip()) {
+            block_start = insn.ip();
+        }
+        let last = instructions
+            .get(i + 1)
+            .is_none_or(|next| leaders.contains(&next.ip()));
+        if !last {
+            continue;
+        }
+
+        let (targets, falls_through) = exits(insn, indirect_targets).unwrap_or((Vec::new(), true));
+        let mut successors: Vec<u64> = targets.into_iter().filter(lands).collect();
+        if falls_through && lands(&insn.next_ip()) {
+            successors.push(insn.next_ip());
+        }
+        successors.sort_unstable();
+        successors.dedup();
+        blocks.insert(
+            block_start,
+            BasicBlock {
+                start: block_start,
+                end: insn.next_ip(),
+                successors,
+            },
+        );
+    }
+
+    // Code after a `ret` or `jmp` that nothing branches to (padding, misdecoded
+    // data) isn't part of the function's graph
+    let mut reachable = Vec::new();
+    let mut seen = HashSet::from([start]);
+    let mut pending = vec![start];
+    while let Some(addr) = pending.pop() {
+        let Some(block) = blocks.remove(&addr) else {
+            continue;
+        };
+        pending.extend(block.successors.iter().filter(|&&s| seen.insert(s)));
+        reachable.push(block);
+    }
+    reachable.sort_unstable_by_key(|block| block.start);
+    Some(reachable)
+}
+
+/// McCabe's cyclomatic complexity of a function's block graph, `edges - nodes + 2`
+///
+/// Blocks without successors (returns, tail calls) are first joined to a single
+/// exit node, as the formula assumes one exit. 1 for straight-line code; each
+/// two-way branch adds one and an n-way switch adds n - 1. `blocks` should all be
+/// reachable from the entry, as [`basic_blocks`] returns them.
+pub fn cyclomatic_complexity(blocks: &[BasicBlock]) -> u32 {
+    let edges: usize = blocks.iter().map(|block| block.successors.len()).sum();
+    let exits = blocks.iter().filter(|b| b.successors.is_empty()).count();
+    let (edges, nodes) = if exits == 0 {
+        (edges, blocks.len())
+    } else {
+        (edges + exits, blocks.len() + 1)
+    };
+    (edges + 2).saturating_sub(nodes) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Arch;
+
+    #[test]
+    fn loops_and_branches_count_towards_complexity() {
+        let arch = Arch::X86_64.support();
+        // 0x1000: test edi, edi
+        // 0x1002: je 0x100b
+        // 0x1004: dec edi          <- loop head
+        // 0x1006: jne 0x1004
+        // 0x1008: xor eax, eax
+        // 0x100a: ret
+        // 0x100b: mov eax, 1
+        // 0x1010: ret
+        // 0x1011: int3             (padding, unreachable)
+        let code = [
+            0x85, 0xff, 0x74, 0x07, 0xff, 0xcf, 0x75, 0xfc, 0x31, 0xc0, 0xc3, 0xb8, 0x01, 0x00,
+            0x00, 0x00, 0xc3, 0xcc,
+        ];
+        let blocks = basic_blocks(&code, 0x1000, arch, &HashMap::new()).unwrap();
+        let shape: Vec<_> = blocks
+            .iter()
+            .map(|b| (b.start, b.end, b.successors.as_slice()))
+            .collect();
+        assert_eq!(
+            shape,
+            [
+                (0x1000, 0x1004, &[0x1004, 0x100b][..]),
+                (0x1004, 0x1008, &[0x1004, 0x1008][..]),
+                (0x1008, 0x100b, &[][..]),
+                (0x100b, 0x1011, &[][..]),
+            ]
+        );
+        assert_eq!(cyclomatic_complexity(&blocks), 3);
+
+        // A switch through a table with three targets: ret; ret; ret
+        let switch = [0xff, 0xe0, 0xc3, 0xc3, 0xc3]; // jmp rax
+        let tables = HashMap::from([(0x2000, vec![0x2002, 0x2003, 0x2004])]);
+        let blocks = basic_blocks(&switch, 0x2000, arch, &tables).unwrap();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(cyclomatic_complexity(&blocks), 3);
+    }
+}
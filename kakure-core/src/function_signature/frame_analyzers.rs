@@ -1,5 +1,9 @@
+pub mod dynsym;
 pub mod eh_frame;
+pub mod heuristic;
+pub mod macho;
 pub mod symtab;
+pub mod wasm;
 
 use std::fmt;
 
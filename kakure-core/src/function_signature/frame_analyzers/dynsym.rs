@@ -0,0 +1,82 @@
+/// Read the `index`-th 32-bit word of `data`
+fn word(data: &[u8], index: usize, little_endian: bool) -> Option<u32> {
+    let bytes = data.get(index * 4..index * 4 + 4)?.try_into().ok()?;
+    Some(if little_endian {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    })
+}
+
+/// Number of dynamic symbols according to a SysV `.hash` table (its `nchain` field)
+///
+/// The hash tables describe `.dynsym` independently of its section header, so they
+/// still bound it when `sh_size` is wrong.
+pub fn sysv_hash_symbol_count(hash: &[u8], little_endian: bool) -> Option<usize> {
+    word(hash, 1, little_endian).map(|nchain| nchain as usize)
+}
+
+/// Number of dynamic symbols according to a `.gnu.hash` table
+///
+/// The table has no count field: symbols below `symoffset` are unhashed, and the
+/// last hashed symbol ends the chain started by the highest bucket, marked by the
+/// low bit of its chain entry. Returns `None` for a truncated table.
+pub fn gnu_hash_symbol_count(gnu_hash: &[u8], is_64: bool, little_endian: bool) -> Option<usize> {
+    let nbuckets = word(gnu_hash, 0, little_endian)? as usize;
+    let symoffset = word(gnu_hash, 1, little_endian)? as usize;
+    let bloom_size = word(gnu_hash, 2, little_endian)? as usize;
+
+    let bloom_words = bloom_size * if is_64 { 2 } else { 1 };
+    let buckets = 4 + bloom_words;
+    let chains = buckets + nbuckets;
+
+    let mut last = 0;
+    for bucket in 0..nbuckets {
+        last = last.max(word(gnu_hash, buckets + bucket, little_endian)? as usize);
+    }
+    if last < symoffset {
+        return Some(symoffset);
+    }
+
+    let mut index = last;
+    loop {
+        let chain = word(gnu_hash, chains + index - symoffset, little_endian)?;
+        if chain & 1 != 0 {
+            return Some(index + 1);
+        }
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn gnu_hash_counts_up_to_the_end_of_the_last_chain() {
+        // 2 buckets, symoffset 1, one 64-bit bloom word (2 words), shift 6
+        let gnu_hash = table(&[
+            2, 1, 1, 6, //
+            0, 0, // bloom
+            1, 3, // buckets
+            0x10, 0x21, 0x30, 0x41, // chains for symbols 1..=4
+        ]);
+        assert_eq!(gnu_hash_symbol_count(&gnu_hash, true, true), Some(5));
+        assert_eq!(gnu_hash_symbol_count(&gnu_hash[..40], true, true), None);
+    }
+
+    #[test]
+    fn empty_gnu_hash_counts_only_unhashed_symbols() {
+        let gnu_hash = table(&[1, 3, 1, 6, 0, 0]);
+        assert_eq!(gnu_hash_symbol_count(&gnu_hash, false, true), Some(3));
+    }
+
+    #[test]
+    fn sysv_hash_count_is_nchain() {
+        assert_eq!(sysv_hash_symbol_count(&table(&[3, 7]), true), Some(7));
+    }
+}
@@ -1,27 +1,442 @@
-use crate::FunctionSignature;
+use crate::{FunctionNaming, FunctionSignature};
+use anyhow::anyhow;
 use anyhow::Result;
-use gimli::{BaseAddresses, EhFrame, NativeEndian, UnwindSection};
-pub fn parse_eh_frame(data: &[u8], base_address: u64) -> Result<Vec<FunctionSignature>> {
+use gimli::{BaseAddresses, EhFrame, EhFrameHdr, Pointer, RunTimeEndian, UnwindSection};
+use std::collections::BTreeMap;
+
+/// A personality routine named by the CIEs in `.eh_frame`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalityPointer {
+    /// The routine itself, or with `indirect` the slot holding its address
+    /// (e.g. `DW.ref.__gxx_personality_v0`)
+    pub address: u64,
+    pub indirect: bool,
+    /// How many FDEs use it
+    pub functions: usize,
+}
+
+/// How to compute the canonical frame address (the caller's stack pointer)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfaRule {
+    /// `register + offset`, e.g. `rsp+8` on entry to an x86-64 function
+    RegisterOffset { register: u16, offset: i64 },
+    /// A DWARF expression, not evaluated here
+    Expression,
+}
+
+/// Where the caller's value of a register can be recovered from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterRule {
+    Undefined,
+    SameValue,
+    /// Saved at `CFA + offset`
+    Offset(i64),
+    /// The value is `CFA + offset` itself
+    ValOffset(i64),
+    /// Held in another register
+    Register(u16),
+    /// Saved at the address computed by a DWARF expression
+    Expression,
+    /// Computed by a DWARF expression
+    ValExpression,
+    Architectural,
+    Constant(u64),
+}
+
+/// The unwind rules in effect for `[start, end)` of a function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfiRow {
+    pub start: u64,
+    pub end: u64,
+    pub cfa: CfaRule,
+    /// DWARF register numbers with their rules; registers not listed keep the
+    /// architecture's default (usually same value)
+    pub registers: Vec<(u16, RegisterRule)>,
+}
+
+/// Run the CFI program of the FDE covering `address` and return every row of its
+/// unwind table, in address order
+pub fn parse_cfi_rows(
+    data: &[u8],
+    base_address: u64,
+    endian: RunTimeEndian,
+    address: u64,
+) -> Result<Vec<CfiRow>> {
+    let eh_frame = EhFrame::new(data, endian);
+    let bases = BaseAddresses::default().set_eh_frame(base_address);
+    let fde = eh_frame
+        .fde_for_address(&bases, address, EhFrame::cie_from_offset)
+        .map_err(|e| anyhow!("no FDE covers {:#x}: {}", address, e))?;
+
+    let mut ctx = gimli::UnwindContext::new();
+    let mut table = gimli::UnwindTable::new(&eh_frame, &bases, &mut ctx, &fde)?;
+    let mut rows = Vec::new();
+    while let Some(row) = table.next_row()? {
+        let cfa = match row.cfa() {
+            gimli::CfaRule::RegisterAndOffset { register, offset } => CfaRule::RegisterOffset {
+                register: register.0,
+                offset: *offset,
+            },
+            gimli::CfaRule::Expression(_) => CfaRule::Expression,
+        };
+        let registers = row
+            .registers()
+            .filter_map(|(register, rule)| {
+                let rule = match rule {
+                    gimli::RegisterRule::Undefined => RegisterRule::Undefined,
+                    gimli::RegisterRule::SameValue => RegisterRule::SameValue,
+                    gimli::RegisterRule::Offset(offset) => RegisterRule::Offset(*offset),
+                    gimli::RegisterRule::ValOffset(offset) => RegisterRule::ValOffset(*offset),
+                    gimli::RegisterRule::Register(other) => RegisterRule::Register(other.0),
+                    gimli::RegisterRule::Expression(_) => RegisterRule::Expression,
+                    gimli::RegisterRule::ValExpression(_) => RegisterRule::ValExpression,
+                    gimli::RegisterRule::Architectural => RegisterRule::Architectural,
+                    gimli::RegisterRule::Constant(value) => RegisterRule::Constant(*value),
+                    // Rules added by later gimli versions
+                    _ => return None,
+                };
+                Some((register.0, rule))
+            })
+            .collect();
+        rows.push(CfiRow {
+            start: row.start_address(),
+            end: row.end_address(),
+            cfa,
+            registers,
+        });
+    }
+    Ok(rows)
+}
+
+/// Collect one `FunctionSignature` per FDE in `.eh_frame`
+///
+/// At most `max_entries` CIE/FDE records are visited, and parsing stops early if
+/// the entry offsets ever fail to advance, so malformed tables cannot stall analysis.
+/// Functions whose FDE has an LSDA are marked `has_exception_handling`.
+pub fn parse_eh_frame(
+    data: &[u8],
+    base_address: u64,
+    endian: RunTimeEndian,
+    max_entries: usize,
+    naming: &FunctionNaming,
+) -> Result<Vec<FunctionSignature>> {
+    Ok(parse_eh_frame_entries(data, base_address, endian, max_entries, naming)?.0)
+}
+
+/// The personality routines used by the FDEs in `.eh_frame`, ordered by address
+///
+/// Bounded by `max_entries` like [`parse_eh_frame`].
+pub fn parse_eh_frame_personalities(
+    data: &[u8],
+    base_address: u64,
+    endian: RunTimeEndian,
+    max_entries: usize,
+) -> Result<Vec<PersonalityPointer>> {
+    let naming = FunctionNaming::default();
+    Ok(parse_eh_frame_entries(data, base_address, endian, max_entries, &naming)?.1)
+}
+
+/// Walk `.eh_frame` once, collecting functions and the personality routines they use
+fn parse_eh_frame_entries(
+    data: &[u8],
+    base_address: u64,
+    endian: RunTimeEndian,
+    max_entries: usize,
+    naming: &FunctionNaming,
+) -> Result<(Vec<FunctionSignature>, Vec<PersonalityPointer>)> {
     let mut signatures = Vec::new();
-    let eh_frame = EhFrame::new(data, NativeEndian);
+    let mut personalities: BTreeMap<(u64, bool), usize> = BTreeMap::new();
+    let eh_frame = EhFrame::new(data, endian);
     let bases = BaseAddresses::default().set_eh_frame(base_address);
 
     let mut entries = eh_frame.entries(&bases);
+    let mut visited = 0;
+    let mut last_offset = None;
     while let Some(entry) = entries.next()? {
+        visited += 1;
+        if visited > max_entries {
+            log::warn!(
+                ".eh_frame has more than {} entries; stopping early",
+                max_entries
+            );
+            break;
+        }
+
+        let offset = match &entry {
+            gimli::CieOrFde::Cie(cie) => cie.offset(),
+            gimli::CieOrFde::Fde(partial_fde) => partial_fde.offset(),
+        };
+        if last_offset.is_some_and(|last| offset <= last) {
+            log::warn!(
+                ".eh_frame entry offset {:#x} did not advance; stopping early",
+                offset
+            );
+            break;
+        }
+        last_offset = Some(offset);
+
         if let gimli::CieOrFde::Fde(partial_fde) = entry {
             if let Ok(fde) = partial_fde.parse(|_, bases, o| eh_frame.cie_from_offset(bases, o)) {
                 let start = fde.initial_address();
                 let size = fde.len();
+                match fde.personality() {
+                    Some(Pointer::Direct(address)) => {
+                        *personalities.entry((address, false)).or_default() += 1
+                    }
+                    Some(Pointer::Indirect(address)) => {
+                        *personalities.entry((address, true)).or_default() += 1
+                    }
+                    None => {}
+                }
                 signatures.push(FunctionSignature {
-                    function_identifier: format!("FUNC_{:#x}", start),
-                    start,
-                    end: start + size,
-                    size,
+                    has_exception_handling: fde.lsda().is_some(),
+                    ..FunctionSignature::new(naming.name(start), start, size)
                 });
             }
         }
     }
 
-    signatures.sort_by_key(|sig| sig.start);
-    Ok(signatures)
+    // Duplicate FDEs for one start (seen with LTO) keep the largest extent
+    signatures.sort_by_key(|sig| (sig.start, std::cmp::Reverse(sig.size)));
+    signatures.dedup_by(|dup, kept| {
+        if dup.start != kept.start {
+            return false;
+        }
+        if dup.size != kept.size {
+            log::warn!(
+                "Duplicate FDEs at {:#x} disagree on length ({} vs {}); keeping {}",
+                kept.start,
+                kept.size,
+                dup.size,
+                kept.size
+            );
+        } else {
+            log::debug!("Skipping duplicate FDE at {:#x}", kept.start);
+        }
+        true
+    });
+
+    let personalities = personalities
+        .into_iter()
+        .map(|((address, indirect), functions)| PersonalityPointer {
+            address,
+            indirect,
+            functions,
+        })
+        .collect();
+    Ok((signatures, personalities))
+}
+
+/// Read function starts from the binary-search table in `.eh_frame_hdr`
+///
+/// The table only records where each FDE's function begins, so this is a cheap way
+/// to enumerate starts (or cross-check `parse_eh_frame`) without decoding any FDEs.
+/// Returns the starts in ascending order.
+pub fn parse_eh_frame_hdr(
+    data: &[u8],
+    base_address: u64,
+    endian: RunTimeEndian,
+    address_size: u8,
+) -> Result<Vec<u64>> {
+    let bases = BaseAddresses::default().set_eh_frame_hdr(base_address);
+    let hdr = EhFrameHdr::new(data, endian).parse(&bases, address_size)?;
+    let Some(table) = hdr.table() else {
+        log::warn!(".eh_frame_hdr has no search table");
+        return Ok(Vec::new());
+    };
+
+    let mut starts = Vec::new();
+    let mut entries = table.iter(&bases);
+    while let Some((initial_location, _fde_address)) = entries.next()? {
+        let start = initial_location
+            .direct()
+            .map_err(|_| anyhow!("indirect initial location in .eh_frame_hdr"))?;
+        starts.push(start);
+    }
+
+    Ok(starts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gimli::write::{
+        Address, CallFrameInstruction, CommonInformationEntry, EhFrame as EhFrameWriter, EndianVec,
+        FrameDescriptionEntry, FrameTable,
+    };
+    use gimli::{Encoding, Format, Register};
+
+    fn build_eh_frame(endian: RunTimeEndian, fdes: &[(u64, u32)]) -> Vec<u8> {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 1,
+            address_size: 8,
+        };
+        let mut cie = CommonInformationEntry::new(encoding, 1, -8, Register(16));
+        cie.add_instruction(CallFrameInstruction::Cfa(Register(7), 8));
+
+        let mut table = FrameTable::default();
+        let cie_id = table.add_cie(cie);
+        for &(start, len) in fdes {
+            table.add_fde(
+                cie_id,
+                FrameDescriptionEntry::new(Address::Constant(start), len),
+            );
+        }
+
+        let mut eh_frame = EhFrameWriter(EndianVec::new(endian));
+        table.write_eh_frame(&mut eh_frame).unwrap();
+        eh_frame.0.into_vec()
+    }
+
+    #[test]
+    fn duplicate_fdes_keep_largest_extent() {
+        let data = build_eh_frame(
+            RunTimeEndian::Little,
+            &[(0x1000, 0x10), (0x1000, 0x20), (0x2000, 0x8)],
+        );
+        let functions = parse_eh_frame(
+            &data,
+            0,
+            RunTimeEndian::Little,
+            usize::MAX,
+            &FunctionNaming::default(),
+        )
+        .unwrap();
+
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].start, 0x1000);
+        assert_eq!(functions[0].size, 0x20);
+        assert_eq!(functions[0].end, 0x1020);
+        assert_eq!(functions[1].start, 0x2000);
+    }
+
+    #[test]
+    fn fde_running_past_the_address_space_becomes_zero_sized() {
+        let data = build_eh_frame(RunTimeEndian::Little, &[(u64::MAX - 0x10, 0x20)]);
+        let functions = parse_eh_frame(
+            &data,
+            0,
+            RunTimeEndian::Little,
+            usize::MAX,
+            &FunctionNaming::default(),
+        )
+        .unwrap();
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].start, u64::MAX - 0x10);
+        assert_eq!(functions[0].end, u64::MAX - 0x10);
+        assert_eq!(functions[0].size, 0);
+    }
+
+    #[test]
+    fn lsda_marks_exception_handling_and_personality_is_counted() {
+        let encoding = Encoding {
+            format: Format::Dwarf32,
+            version: 1,
+            address_size: 8,
+        };
+        // Like GCC, functions without landing pads get a CIE without personality or LSDA
+        let plain = CommonInformationEntry::new(encoding, 1, -8, Register(16));
+        let mut cxx = plain.clone();
+        cxx.personality = Some((gimli::DW_EH_PE_absptr, Address::Constant(0x5000)));
+        cxx.lsda_encoding = Some(gimli::DW_EH_PE_absptr);
+
+        let mut table = FrameTable::default();
+        let cxx_id = table.add_cie(cxx);
+        let plain_id = table.add_cie(plain);
+        let mut with_lsda = FrameDescriptionEntry::new(Address::Constant(0x1000), 0x10);
+        with_lsda.lsda = Some(Address::Constant(0x6000));
+        table.add_fde(cxx_id, with_lsda);
+        table.add_fde(
+            plain_id,
+            FrameDescriptionEntry::new(Address::Constant(0x2000), 0x10),
+        );
+
+        let mut eh_frame = EhFrameWriter(EndianVec::new(RunTimeEndian::Little));
+        table.write_eh_frame(&mut eh_frame).unwrap();
+        let data = eh_frame.0.into_vec();
+
+        let functions = parse_eh_frame(
+            &data,
+            0,
+            RunTimeEndian::Little,
+            usize::MAX,
+            &FunctionNaming::default(),
+        )
+        .unwrap();
+        assert!(functions[0].has_exception_handling);
+        assert!(!functions[1].has_exception_handling);
+
+        let personalities =
+            parse_eh_frame_personalities(&data, 0, RunTimeEndian::Little, usize::MAX).unwrap();
+        assert_eq!(
+            personalities,
+            [PersonalityPointer {
+                address: 0x5000,
+                indirect: false,
+                functions: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn cfi_rows_come_from_the_covering_fde() {
+        let data = build_eh_frame(RunTimeEndian::Little, &[(0x1000, 0x10), (0x2000, 0x8)]);
+
+        let rows = parse_cfi_rows(&data, 0, RunTimeEndian::Little, 0x2004).unwrap();
+        assert_eq!(
+            rows,
+            [CfiRow {
+                start: 0x2000,
+                end: 0x2008,
+                cfa: CfaRule::RegisterOffset {
+                    register: 7,
+                    offset: 8
+                },
+                registers: Vec::new(),
+            }]
+        );
+        assert!(parse_cfi_rows(&data, 0, RunTimeEndian::Little, 0x3000).is_err());
+    }
+
+    #[test]
+    fn big_endian_tables_are_read_in_their_byte_order() {
+        let data = build_eh_frame(RunTimeEndian::Big, &[(0x1000, 0x10)]);
+
+        let functions = parse_eh_frame(
+            &data,
+            0,
+            RunTimeEndian::Big,
+            usize::MAX,
+            &FunctionNaming::default(),
+        )
+        .unwrap();
+        assert_eq!((functions[0].start, functions[0].size), (0x1000, 0x10));
+
+        let rows = parse_cfi_rows(&data, 0, RunTimeEndian::Big, 0x1008).unwrap();
+        assert_eq!((rows[0].start, rows[0].end), (0x1000, 0x1010));
+    }
+
+    #[test]
+    fn rebased_analysis_slides_absolute_and_pc_relative_fdes() {
+        let bytes = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/x86_64_eh_frame.elf"
+        ));
+        let mut analysis = crate::BinaryAnalysis::from_bytes(bytes.to_vec()).unwrap();
+        analysis.rebase(0x100000).unwrap();
+        analysis.analyze_eh_frame().unwrap();
+
+        // `_start`'s FDE is DW_EH_PE_absptr, `helper`'s DW_EH_PE_pcrel
+        let functions: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| (f.start, f.size))
+            .collect();
+        assert_eq!(functions, [(0x500078, 6), (0x50007e, 6)]);
+
+        let rows = analysis.cfi_rules(0x500078).unwrap();
+        assert_eq!((rows[0].start, rows[0].end), (0x500078, 0x50007e));
+    }
 }
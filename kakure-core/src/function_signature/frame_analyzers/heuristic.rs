@@ -0,0 +1,78 @@
+use crate::{ArchSupport, FunctionNaming, FunctionSignature};
+
+/// Scans `data` (mapped at `base_address`) for common function prologues.
+///
+/// This is lossy: it finds candidate starts only, so every signature has a
+/// size of zero. Consecutive prologue pieces (e.g. `endbr64` followed by
+/// `push rbp; mov rbp, rsp`) are reported once, at the first instruction.
+pub fn parse_prologues(
+    data: &[u8],
+    base_address: u64,
+    arch: &dyn ArchSupport,
+    naming: &FunctionNaming,
+) -> Vec<FunctionSignature> {
+    let patterns = arch.prologues();
+    let mut signatures = Vec::new();
+    if patterns.is_empty() {
+        log::warn!("No prologue patterns known for this architecture");
+        return signatures;
+    }
+
+    let mut last_end = None;
+    let mut offset = 0;
+    while offset < data.len() {
+        let window = &data[offset..];
+        let matched = patterns
+            .iter()
+            .filter(|p| offset % p.align == 0)
+            .find(|p| window.starts_with(p.bytes))
+            .map(|p| p.bytes.len())
+            .or_else(|| {
+                (offset % arch.instruction_alignment() == 0)
+                    .then(|| arch.frame_setup_len(window))
+                    .flatten()
+            });
+
+        match matched {
+            Some(len) => {
+                if last_end != Some(offset) {
+                    // Past the end of the address space (a raw blob at a high base)
+                    // every later candidate would overflow too
+                    let Some(start) = base_address.checked_add(offset as u64) else {
+                        log::warn!(
+                            "Prologue scan from {:#x} ran past the end of the address space",
+                            base_address
+                        );
+                        break;
+                    };
+                    signatures.push(FunctionSignature::new(naming.name(start), start, 0));
+                }
+                offset += len;
+                last_end = Some(offset);
+            }
+            None => offset += 1,
+        }
+    }
+
+    signatures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Arch;
+
+    #[test]
+    fn scan_stops_at_the_end_of_the_address_space() {
+        // push rbp; mov rbp, rsp, twice
+        let code = [0x55, 0x48, 0x89, 0xe5, 0xcc, 0x55, 0x48, 0x89, 0xe5];
+        let functions = parse_prologues(
+            &code,
+            u64::MAX - 4,
+            Arch::X86_64.support(),
+            &FunctionNaming::default(),
+        );
+        let starts: Vec<_> = functions.iter().map(|f| f.start).collect();
+        assert_eq!(starts, [u64::MAX - 4]);
+    }
+}
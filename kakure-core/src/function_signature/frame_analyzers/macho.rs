@@ -0,0 +1,62 @@
+use crate::symtab::SymbolBinding;
+use crate::{FunctionNaming, FunctionSignature, KSection};
+use anyhow::Result;
+use goblin::mach::symbols::{N_EXT, N_SECT};
+use goblin::mach::MachO;
+
+/// Build function signatures from the Mach-O `LC_SYMTAB` nlist entries
+///
+/// nlist entries carry no size, so each function extends to the next symbol in
+/// the same section (or to the section end). Only symbols defined in executable
+/// sections are kept; `sections` must be in load-command order, which is how
+/// `n_sect` ordinals are numbered.
+pub fn parse_macho_symtab(
+    macho: &MachO,
+    sections: &[KSection],
+    naming: &FunctionNaming,
+) -> Result<Vec<FunctionSignature>> {
+    let mut symbols = Vec::new();
+    for symbol in macho.symbols() {
+        let (name, nlist) = symbol?;
+        if nlist.is_stab() || nlist.get_type() != N_SECT || nlist.n_sect == 0 {
+            continue;
+        }
+        let Some(section) = sections.get(nlist.n_sect - 1) else {
+            continue;
+        };
+        if section.is_executable() {
+            symbols.push((nlist.n_value, nlist.n_sect, name, nlist.n_type & N_EXT != 0));
+        }
+    }
+
+    symbols.sort_by_key(|&(addr, _, _, _)| addr);
+    symbols.dedup_by_key(|&mut (addr, _, _, _)| addr);
+
+    let mut signatures = Vec::with_capacity(symbols.len());
+    for (i, &(start, sect, name, external)) in symbols.iter().enumerate() {
+        let section = &sections[sect - 1];
+        let end = symbols
+            .get(i + 1)
+            .filter(|&&(_, next_sect, _, _)| next_sect == sect)
+            .map(|&(next, _, _, _)| next)
+            .unwrap_or(section.vma_range().end);
+
+        let function_identifier = if name.is_empty() {
+            naming.name(start)
+        } else {
+            name.to_string()
+        };
+
+        signatures.push(FunctionSignature {
+            // N_EXT marks an exported symbol; without it the symbol is file-local
+            binding: Some(if external {
+                SymbolBinding::Global
+            } else {
+                SymbolBinding::Local
+            }),
+            ..FunctionSignature::new(function_identifier, start, end.saturating_sub(start))
+        });
+    }
+
+    Ok(signatures)
+}
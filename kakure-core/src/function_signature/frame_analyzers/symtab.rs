@@ -1,118 +1,205 @@
-use crate::FunctionSignature;
-use anyhow::bail;
-use byteorder::{ReadBytesExt, LE};
-use goblin::elf::sym::STT_FUNC;
-use goblin::elf32::section_header::SHN_UNDEF;
-use std::io::Cursor;
-use std::mem;
-
-#[repr(C)]
-#[derive(Debug, Clone, Copy)]
-pub struct Elf64Sym {
-    pub st_name: u32,
-    pub st_info: u8,
-    pub st_other: u8,
-    pub st_shndx: u16,
-    pub st_value: u64,
-    pub st_size: u64,
-}
-
-impl Elf64Sym {
-    pub fn from_section(symtab_data: &[u8]) -> anyhow::Result<Vec<Elf64Sym>> {
-        let num_symbols = symtab_data.len() / size_of::<Elf64Sym>();
-
-        if symtab_data.len() % std::mem::size_of::<Elf64Sym>() != 0 {
-            bail!("Invalid symtab size for 64-bit");
-        }
-
-        let mut signatures = Vec::with_capacity(num_symbols);
-
-        let mut reader = Cursor::new(symtab_data);
-
-        for i in 0..num_symbols {
-            let offset = i * std::mem::size_of::<Elf64Sym>();
-            let sym_bytes = &symtab_data[offset..offset + std::mem::size_of::<Elf64Sym>()];
-
-            let st_name = reader.read_u32::<LE>()?;
-            let st_info = reader.read_u8()?;
-            let st_other = reader.read_u8()?;
-            let st_shndx = reader.read_u16::<LE>()?;
-            let st_value = reader.read_u64::<LE>()?;
-            let st_size = reader.read_u64::<LE>()?;
-
-            if st_shndx == SHN_UNDEF as u16 || st_value == 0 || st_size == 0 {
-                continue;
-            }
-
-            let symbol = Self {
-                st_name,
-                st_info,
-                st_other,
-                st_shndx,
-                st_value,
-                st_size,
-            };
-
-            signatures.push(symbol);
-        }
-        Ok(signatures)
-    }
-
-    pub fn name_from_symtab(&self, strtab_data: &[u8]) -> anyhow::Result<String> {
-        let name = if (self.st_name as usize) < strtab_data.len() {
-            let name_start = self.st_name as usize;
-            let name_end = strtab_data[name_start..]
-                .iter()
-                .position(|&b| b == 0)
-                .map(|pos| name_start + pos)
-                .unwrap_or(strtab_data.len());
-
-            std::str::from_utf8(&strtab_data[name_start..name_end]).unwrap_or("<invalid_utf8>")
-        } else {
-            "<invalid_name>"
-        };
-
-        let function_identifier = if name.is_empty() {
-            format!("FUNC_{:#x}", self.st_value)
-        } else {
-            name.to_string()
-        };
-
-        Ok(function_identifier)
-    }
+use crate::{FunctionNaming, FunctionSignature};
+use goblin::elf::section_header::SHN_LORESERVE;
+use goblin::elf::sym::{STT_FUNC, STT_GNU_IFUNC, STT_OBJECT, STT_TLS};
+
+pub use crate::parse::{Elf32Sym, Elf64Sym, SymbolBinding, SymbolVisibility};
+
+/// A defined `STT_OBJECT` symbol (global variable, table, string constant)
+#[derive(Debug, Clone)]
+pub struct DataSymbol {
+    pub name: String,
+    pub address: u64,
+    pub size: u64,
 }
 
+/// Build a `FunctionSignature` for every code symbol (`STT_FUNC` or `STT_GNU_IFUNC`)
+///
+/// Objects, sections, files and TLS symbols are skipped; see `parse_data_symbols_64`.
 pub fn parse_symtab_64(
     symbols: Vec<Elf64Sym>,
     strtab_data: &[u8],
+    naming: &FunctionNaming,
 ) -> anyhow::Result<Vec<FunctionSignature>> {
     let mut signatures = Vec::with_capacity(symbols.len());
-    for symbol in symbols {
-        let name = if (symbol.st_name as usize) < strtab_data.len() {
-            let name_start = symbol.st_name as usize;
-            let name_end = strtab_data[name_start..]
-                .iter()
-                .position(|&b| b == 0)
-                .map(|pos| name_start + pos)
-                .unwrap_or(strtab_data.len());
-
-            std::str::from_utf8(&strtab_data[name_start..name_end]).unwrap_or("<invalid_utf8>")
-        } else {
-            "<invalid_name>"
-        };
+    for symbol in symbols
+        .into_iter()
+        .filter(|symbol| matches!(symbol.sym_type(), STT_FUNC | STT_GNU_IFUNC))
+    {
+        let name = symbol.raw_name(strtab_data);
 
         let function_identifier = if name.is_empty() {
-            format!("FUNC_{:#x}", symbol.st_value)
+            naming.name(symbol.st_value)
         } else {
             name.to_string()
         };
 
         signatures.push(FunctionSignature {
-            function_identifier,
-            start: symbol.st_value as u64,
-            end: (symbol.st_value + symbol.st_size) as u64,
-            size: symbol.st_size as u64,
+            visibility: Some(symbol.visibility()),
+            binding: Some(symbol.binding()),
+            is_ifunc: symbol.sym_type() == STT_GNU_IFUNC,
+            section: (u32::from(symbol.st_shndx) < SHN_LORESERVE).then_some(symbol.st_shndx.into()),
+            ..FunctionSignature::new(function_identifier, symbol.st_value, symbol.st_size)
         });
     }
     Ok(signatures)
 }
+
+/// Collect the `STT_OBJECT` (data) symbols, skipping code, sections, files and TLS
+pub fn parse_data_symbols_64(symbols: &[Elf64Sym], strtab_data: &[u8]) -> Vec<DataSymbol> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.sym_type() == STT_OBJECT)
+        .map(|symbol| {
+            let name = symbol.raw_name(strtab_data);
+            DataSymbol {
+                name: if name.is_empty() {
+                    format!("DATA_{:#x}", symbol.st_value)
+                } else {
+                    name.to_string()
+                },
+                address: symbol.st_value,
+                size: symbol.st_size,
+            }
+        })
+        .collect()
+}
+
+/// Collect the `STT_TLS` (thread-local) symbols
+///
+/// For executables and shared objects `address` is the variable's offset within the
+/// module's TLS block, not a virtual address.
+pub fn parse_tls_symbols_64(symbols: &[Elf64Sym], strtab_data: &[u8]) -> Vec<DataSymbol> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.sym_type() == STT_TLS)
+        .map(|symbol| {
+            let name = symbol.raw_name(strtab_data);
+            DataSymbol {
+                name: if name.is_empty() {
+                    format!("TLS_{:#x}", symbol.st_value)
+                } else {
+                    name.to_string()
+                },
+                address: symbol.st_value,
+                size: symbol.st_size,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sym(st_name: u32, st_type: u8, st_value: u64) -> Elf64Sym {
+        Elf64Sym {
+            st_name,
+            st_info: st_type,
+            st_other: 0,
+            st_shndx: 1,
+            st_value,
+            st_size: 8,
+        }
+    }
+
+    #[test]
+    fn only_code_symbols_become_functions() {
+        let strtab = b"\0main\0global_data\0resolver\0";
+        let symbols = vec![
+            sym(1, STT_FUNC, 0x1000),
+            sym(6, STT_OBJECT, 0x4000),
+            sym(18, STT_GNU_IFUNC, 0x1100),
+        ];
+
+        let functions =
+            parse_symtab_64(symbols.clone(), strtab, &FunctionNaming::default()).unwrap();
+        let names: Vec<_> = functions
+            .iter()
+            .map(|f| f.function_identifier.as_str())
+            .collect();
+        assert_eq!(names, ["main", "resolver"]);
+
+        let data = parse_data_symbols_64(&symbols, strtab);
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].name, "global_data");
+    }
+
+    #[test]
+    fn symbol_running_past_the_address_space_becomes_zero_sized() {
+        let mut huge = sym(1, STT_FUNC, u64::MAX - 4);
+        huge.st_size = 0x10;
+        let mut last = sym(1, STT_FUNC, u64::MAX - 8);
+        last.st_size = 8;
+
+        let functions =
+            parse_symtab_64(vec![huge, last], b"\0f\0", &FunctionNaming::default()).unwrap();
+        assert_eq!(
+            (functions[0].start, functions[0].end, functions[0].size),
+            (u64::MAX - 4, u64::MAX - 4, 0)
+        );
+        assert_eq!(
+            (functions[1].start, functions[1].end, functions[1].size),
+            (u64::MAX - 8, u64::MAX, 8)
+        );
+    }
+
+    #[test]
+    fn tls_symbol_at_offset_zero_is_kept() {
+        let mut record = vec![0u8; Elf64Sym::SIZE];
+        record[0] = 1; // st_name
+        record[4] = STT_TLS;
+        record[6] = 19; // st_shndx
+        record[16] = 4; // st_size
+        let symbols = Elf64Sym::from_section(&record).unwrap();
+
+        let tls = parse_tls_symbols_64(&symbols, b"\0counter\0");
+        assert_eq!(tls.len(), 1);
+        assert_eq!((tls[0].name.as_str(), tls[0].address), ("counter", 0));
+        assert!(parse_data_symbols_64(&symbols, b"\0counter\0").is_empty());
+    }
+
+    #[test]
+    fn undefined_symbols_are_only_kept_when_asked_for() {
+        // The null symbol, then `puts` referenced but not defined
+        let mut records = vec![0u8; 2 * Elf64Sym::SIZE];
+        records[Elf64Sym::SIZE] = 1; // st_name
+        records[Elf64Sym::SIZE + 4] = 0x12; // STB_GLOBAL, STT_FUNC
+
+        assert!(Elf64Sym::from_section(&records).unwrap().is_empty());
+        let all = Elf64Sym::all_from_section_endian(&records, true).unwrap();
+        let undefined: Vec<_> = all
+            .iter()
+            .filter(|sym| sym.is_undefined())
+            .map(|sym| sym.raw_name(b"\0puts\0"))
+            .collect();
+        assert_eq!(undefined, ["", "puts"]);
+    }
+
+    #[test]
+    fn elf32_symbols_route_through_the_32_bit_layout() {
+        let bytes = include_bytes!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/x86_32_symtab.elf"
+        ));
+        let mut analysis = crate::BinaryAnalysis::from_bytes(bytes.to_vec()).unwrap();
+        assert!(!analysis.header.is_64());
+
+        analysis.analyze_symtab().unwrap();
+        let functions: Vec<_> = analysis
+            .functions()
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.start, f.size))
+            .collect();
+        assert_eq!(
+            functions,
+            [("_start", 0x8048054, 10), ("helper", 0x804805e, 6)]
+        );
+
+        let data = analysis.data_symbols().unwrap();
+        assert_eq!(data.len(), 1);
+        assert_eq!(
+            (data[0].name.as_str(), data[0].address),
+            ("counter", 0x8048064)
+        );
+    }
+}
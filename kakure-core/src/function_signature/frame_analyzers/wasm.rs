@@ -0,0 +1,136 @@
+use crate::{FunctionNaming, FunctionSignature};
+use anyhow::Result;
+use std::collections::HashMap;
+use wasmparser::{ExternalKind, KnownCustom, Name, Parser, Payload, TypeRef};
+
+/// Build function signatures from a WebAssembly module's code section
+///
+/// Each function body becomes one function placed at its file offset, since a
+/// module has no address space of its own. Names come from the `name` custom
+/// section, then from function exports, then from `naming`. Imported functions
+/// have no body and are skipped, but they still take up the low function
+/// indices, which the name section is keyed on.
+pub fn parse_wasm_functions(
+    data: &[u8],
+    naming: &FunctionNaming,
+) -> Result<Vec<FunctionSignature>> {
+    let module = WasmModule::parse(data)?;
+    let signatures = module
+        .bodies
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, size))| {
+            let index = module.imported + i as u32;
+            let name = module
+                .names
+                .get(&index)
+                .or_else(|| module.exports.get(&index))
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| naming.name(start));
+            FunctionSignature::new(name, start, size)
+        })
+        .collect();
+    Ok(signatures)
+}
+
+/// File offset of the body of the module's start function, if it has one
+///
+/// `None` also when the start function is an import, which has no body.
+pub fn wasm_start_offset(data: &[u8]) -> Result<Option<u64>> {
+    let module = WasmModule::parse(data)?;
+    Ok(module
+        .start
+        .and_then(|func| func.checked_sub(module.imported))
+        .and_then(|i| module.bodies.get(i as usize))
+        .map(|&(start, _)| start))
+}
+
+/// The parts of a module the function analysis needs
+#[derive(Default)]
+struct WasmModule<'a> {
+    /// Number of imported functions, which come first in the function index space
+    imported: u32,
+    /// `(file offset, size)` of each defined function body, in index order
+    bodies: Vec<(u64, u64)>,
+    names: HashMap<u32, &'a str>,
+    exports: HashMap<u32, &'a str>,
+    start: Option<u32>,
+}
+
+impl<'a> WasmModule<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut module = WasmModule::default();
+        for payload in Parser::new(0).parse_all(data) {
+            match payload? {
+                Payload::ImportSection(imports) => {
+                    for import in imports {
+                        if matches!(import?.ty, TypeRef::Func(_) | TypeRef::FuncExact(_)) {
+                            module.imported += 1;
+                        }
+                    }
+                }
+                Payload::ExportSection(exports) => {
+                    for export in exports {
+                        let export = export?;
+                        if export.kind == ExternalKind::Func {
+                            module.exports.entry(export.index).or_insert(export.name);
+                        }
+                    }
+                }
+                Payload::StartSection { func, .. } => module.start = Some(func),
+                Payload::CodeSectionEntry(body) => {
+                    let range = body.range();
+                    module
+                        .bodies
+                        .push((range.start as u64, (range.end - range.start) as u64));
+                }
+                Payload::CustomSection(custom) => {
+                    if let KnownCustom::Name(names) = custom.as_known() {
+                        // The name section is only advisory; a malformed one just loses names
+                        for name in names.into_iter().map_while(|n| n.ok()) {
+                            if let Name::Function(map) = name {
+                                for naming in map.into_iter().map_while(|n| n.ok()) {
+                                    module.names.insert(naming.index, naming.name);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A module importing `env.log`, defining two functions and naming only the
+    /// second; the first is exported as `run` and is the start function.
+    const MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version 1
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type: () -> ()
+        0x02, 0x0b, 0x01, 0x03, b'e', b'n', b'v', 0x03, b'l', b'o', b'g', 0x00,
+        0x00, // import env.log: func type 0
+        0x03, 0x03, 0x02, 0x00, 0x00, // function: two of type 0
+        0x07, 0x07, 0x01, 0x03, b'r', b'u', b'n', 0x00, 0x01, // export "run" = func 1
+        0x08, 0x01, 0x01, // start: func 1
+        0x0a, 0x09, 0x02, 0x02, 0x00, 0x0b, 0x04, 0x00, 0x10, 0x00,
+        0x0b, // code: `end`; `call 0; end`
+        0x00, 0x0e, 0x04, b'n', b'a', b'm', b'e', 0x01, 0x07, 0x01, 0x02, 0x04, b'h', b'e', b'l',
+        b'p', // name: func 2 = "help"
+    ];
+
+    #[test]
+    fn functions_are_named_from_the_name_section_then_exports() {
+        let functions = parse_wasm_functions(MODULE, &FunctionNaming::default()).unwrap();
+        let found: Vec<_> = functions
+            .iter()
+            .map(|f| (f.function_identifier.as_str(), f.start, f.size))
+            .collect();
+        assert_eq!(found, [("run", 0x30, 2), ("help", 0x33, 4)]);
+        assert_eq!(wasm_start_offset(MODULE).unwrap(), Some(0x30));
+    }
+}
@@ -1,6 +1,11 @@
 use std::io;
 
 pub mod elf;
+pub mod macho;
+pub mod raw;
+pub mod wasm;
+
+pub use crate::parse::ObjectType;
 
 pub trait Header: std::fmt::Debug + Send + Sync {
     /// Returns the virtual address of the entry point.
@@ -9,6 +14,11 @@ pub trait Header: std::fmt::Debug + Send + Sync {
     /// Returns the machine architecture identifier.
     fn machine(&self) -> u16;
 
+    /// Returns the architecture name for `machine()`, e.g. "X86_64" or "AARCH64".
+    fn machine_name(&self) -> &'static str {
+        goblin::elf::header::machine_to_str(self.machine())
+    }
+
     /// Returns true if this is a 64-bit binary.
     fn is_64(&self) -> bool;
 
@@ -18,6 +28,24 @@ pub trait Header: std::fmt::Debug + Send + Sync {
     /// Returns true if the binary represents an executable (vs object/lib).
     fn is_executable(&self) -> bool;
 
+    /// Returns the kind of object, in ELF `e_type` terms.
+    fn object_type(&self) -> ObjectType;
+
+    /// Returns true if multi-byte fields are stored little-endian.
+    fn is_little_endian(&self) -> bool {
+        true
+    }
+
+    /// Returns the OS/ABI the binary targets, e.g. "SYSV" or "Darwin".
+    fn os_abi(&self) -> &'static str {
+        "none"
+    }
+
+    /// Returns the kind of file, e.g. "executable", "shared object" or "relocatable".
+    fn file_type(&self) -> &'static str {
+        "unknown"
+    }
+
     /// Creates a new object by reading
     fn from_reader<R: io::Read + io::Seek>(cur: &mut R) -> anyhow::Result<Self>
     where
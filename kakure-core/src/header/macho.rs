@@ -0,0 +1,130 @@
+use crate::header::{Header, ObjectType};
+use byteorder::{ReadBytesExt, LE};
+use goblin::elf::header::{EM_386, EM_AARCH64, EM_ARM, EM_NONE, EM_X86_64};
+use goblin::mach::cputype::{CPU_TYPE_ARM, CPU_TYPE_ARM64, CPU_TYPE_X86, CPU_TYPE_X86_64};
+use goblin::mach::header::{
+    MH_BUNDLE, MH_CIGAM, MH_CIGAM_64, MH_CORE, MH_DYLIB, MH_DYLINKER, MH_EXECUTE, MH_MAGIC_64,
+    MH_OBJECT,
+};
+use std::io;
+
+/// Represents the Mach-O header (`mach_header` / `mach_header_64`).
+///
+/// The entry point does not live in the header itself; it comes from the
+/// `LC_MAIN` / `LC_UNIXTHREAD` load command and is filled in after parsing.
+///
+/// Reference: `<mach-o/loader.h>`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MachHeader {
+    /// Magic number (`MH_MAGIC`, `MH_MAGIC_64`, or their byte-swapped forms).
+    pub magic: u32,
+
+    /// CPU type (e.g. `CPU_TYPE_X86_64`, `CPU_TYPE_ARM64`).
+    pub cputype: u32,
+
+    /// Machine-specific CPU subtype.
+    pub cpusubtype: u32,
+
+    /// File type (e.g. `MH_EXECUTE`, `MH_DYLIB`, `MH_OBJECT`).
+    pub filetype: u32,
+
+    /// Number of load commands.
+    pub ncmds: u32,
+
+    /// Total size of all load commands.
+    pub sizeofcmds: u32,
+
+    /// Header flags (`MH_PIE`, `MH_TWOLEVEL`, ...).
+    pub flags: u32,
+
+    /// Virtual address of the entry point, resolved from the load commands.
+    pub entry: u64,
+}
+
+impl MachHeader {
+    pub fn from_goblin(macho: &goblin::mach::MachO) -> Self {
+        MachHeader {
+            magic: macho.header.magic,
+            cputype: macho.header.cputype,
+            cpusubtype: macho.header.cpusubtype,
+            filetype: macho.header.filetype,
+            ncmds: macho.header.ncmds as u32,
+            sizeofcmds: macho.header.sizeofcmds,
+            flags: macho.header.flags,
+            entry: macho.entry,
+        }
+    }
+}
+
+impl Header for MachHeader {
+    fn entry_point(&self) -> u64 {
+        self.entry
+    }
+
+    /// Maps the Mach-O CPU type onto the equivalent ELF `e_machine` value.
+    fn machine(&self) -> u16 {
+        match self.cputype {
+            CPU_TYPE_X86_64 => EM_X86_64,
+            CPU_TYPE_X86 => EM_386,
+            CPU_TYPE_ARM64 => EM_AARCH64,
+            CPU_TYPE_ARM => EM_ARM,
+            _ => EM_NONE,
+        }
+    }
+
+    fn is_64(&self) -> bool {
+        self.magic == MH_MAGIC_64 || self.magic == MH_CIGAM_64
+    }
+
+    fn format_name(&self) -> &'static str {
+        "Mach-O"
+    }
+
+    fn is_executable(&self) -> bool {
+        self.filetype == MH_EXECUTE
+    }
+
+    fn is_little_endian(&self) -> bool {
+        self.magic != MH_CIGAM && self.magic != MH_CIGAM_64
+    }
+
+    fn os_abi(&self) -> &'static str {
+        "Darwin"
+    }
+
+    /// Dylibs, bundles and the dynamic linker count as [`ObjectType::Dyn`]
+    fn object_type(&self) -> ObjectType {
+        match self.filetype {
+            MH_OBJECT => ObjectType::Rel,
+            MH_EXECUTE => ObjectType::Exec,
+            MH_DYLIB | MH_BUNDLE | MH_DYLINKER => ObjectType::Dyn,
+            MH_CORE => ObjectType::Core,
+            _ => ObjectType::None,
+        }
+    }
+
+    fn file_type(&self) -> &'static str {
+        match self.filetype {
+            MH_OBJECT => "object",
+            MH_EXECUTE => "executable",
+            MH_DYLIB => "dylib",
+            MH_BUNDLE => "bundle",
+            MH_DYLINKER => "dynamic linker",
+            MH_CORE => "core",
+            _ => "unknown",
+        }
+    }
+
+    fn from_reader<R: io::Read + io::Seek>(cur: &mut R) -> anyhow::Result<MachHeader> {
+        Ok(MachHeader {
+            magic: cur.read_u32::<LE>()?,
+            cputype: cur.read_u32::<LE>()?,
+            cpusubtype: cur.read_u32::<LE>()?,
+            filetype: cur.read_u32::<LE>()?,
+            ncmds: cur.read_u32::<LE>()?,
+            sizeofcmds: cur.read_u32::<LE>()?,
+            flags: cur.read_u32::<LE>()?,
+            entry: 0,
+        })
+    }
+}
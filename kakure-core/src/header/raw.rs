@@ -0,0 +1,51 @@
+use crate::header::{Header, ObjectType};
+use anyhow::bail;
+use goblin::elf::header::{EM_386, EM_ARM};
+use std::io;
+
+/// Synthetic header for container-less inputs such as dumped shellcode.
+///
+/// There is nothing to parse; the caller supplies the architecture and the
+/// address the blob is mapped at, which doubles as its entry point.
+#[derive(Debug, Clone, Copy)]
+pub struct RawHeader {
+    /// Target architecture as an ELF `e_machine` value.
+    pub machine: u16,
+
+    /// Address the first byte of the blob is mapped at.
+    pub base_address: u64,
+}
+
+impl Header for RawHeader {
+    fn entry_point(&self) -> u64 {
+        self.base_address
+    }
+
+    fn machine(&self) -> u16 {
+        self.machine
+    }
+
+    fn is_64(&self) -> bool {
+        !matches!(self.machine, EM_386 | EM_ARM)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "RAW"
+    }
+
+    fn is_executable(&self) -> bool {
+        true
+    }
+
+    fn object_type(&self) -> ObjectType {
+        ObjectType::Exec
+    }
+
+    fn file_type(&self) -> &'static str {
+        "raw blob"
+    }
+
+    fn from_reader<R: io::Read + io::Seek>(_cur: &mut R) -> anyhow::Result<RawHeader> {
+        bail!("Raw blobs have no header to read; use BinaryAnalysis::open_raw")
+    }
+}
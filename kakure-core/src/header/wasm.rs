@@ -0,0 +1,78 @@
+use crate::header::{Header, ObjectType};
+use anyhow::bail;
+use byteorder::{ReadBytesExt, LE};
+use goblin::elf::header::EM_NONE;
+use std::io;
+
+/// Magic bytes opening every WebAssembly binary, `\0asm`
+pub const WASM_MAGIC: [u8; 4] = *b"\0asm";
+
+/// Represents the WebAssembly module preamble (magic and version).
+///
+/// A module has no load address; kakure places its sections and functions at
+/// their file offsets, so the entry point is the file offset of the start
+/// function's body, filled in after parsing.
+///
+/// Reference: WebAssembly Core Specification, "Binary Format: Modules"
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmHeader {
+    /// Binary format version; 1 for every core module so far.
+    pub version: u32,
+
+    /// File offset of the start function's body, or 0 without a start section.
+    pub entry: u64,
+}
+
+impl Header for WasmHeader {
+    fn entry_point(&self) -> u64 {
+        self.entry
+    }
+
+    /// WebAssembly has no ELF machine number.
+    fn machine(&self) -> u16 {
+        EM_NONE
+    }
+
+    fn machine_name(&self) -> &'static str {
+        "WASM"
+    }
+
+    /// Core modules use 32-bit linear memory addressing.
+    fn is_64(&self) -> bool {
+        false
+    }
+
+    fn format_name(&self) -> &'static str {
+        "WASM"
+    }
+
+    /// True when the module has a start function that runs on instantiation
+    fn is_executable(&self) -> bool {
+        self.entry != 0
+    }
+
+    /// Modules are never mapped at an address, so none of the ELF kinds fit
+    fn object_type(&self) -> ObjectType {
+        ObjectType::None
+    }
+
+    fn file_type(&self) -> &'static str {
+        "module"
+    }
+
+    fn from_reader<R: io::Read + io::Seek>(cur: &mut R) -> anyhow::Result<WasmHeader> {
+        let mut magic = [0u8; 4];
+        cur.read_exact(&mut magic)?;
+        if magic != WASM_MAGIC {
+            bail!("Not a WebAssembly module (bad magic {:02x?})", magic);
+        }
+        let version = cur.read_u32::<LE>()?;
+        if version != 1 {
+            bail!(
+                "Unsupported WebAssembly version {:#x}; only core modules (version 1) are handled",
+                version
+            );
+        }
+        Ok(WasmHeader { version, entry: 0 })
+    }
+}
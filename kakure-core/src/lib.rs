@@ -1,8 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod parse;
+
+#[cfg(feature = "std")]
+pub mod arch;
+#[cfg(feature = "std")]
 pub mod binary;
+#[cfg(feature = "std")]
+pub mod demangle;
+#[cfg(feature = "std")]
+pub mod digest;
+#[cfg(feature = "std")]
 pub mod function_signature;
-mod header;
+#[cfg(feature = "std")]
+pub mod header;
+#[cfg(feature = "std")]
 pub mod sections;
 
+#[cfg(feature = "std")]
+pub use arch::{Arch, ArchSupport};
+#[cfg(feature = "std")]
 pub use binary::*;
+#[cfg(feature = "std")]
+pub use demangle::*;
+#[cfg(feature = "std")]
+pub use digest::*;
+#[cfg(feature = "std")]
 pub use function_signature::*;
+#[cfg(feature = "std")]
 pub use sections::*;
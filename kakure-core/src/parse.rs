@@ -0,0 +1,21 @@
+//! ELF structures decoded straight from byte slices
+//!
+//! Nothing here touches `std::io` or `anyhow`: records are read through a
+//! [`ByteCursor`] and failures are a plain [`ParseError`]. With
+//! `default-features = false` this is all the crate builds, as a `no_std` + `alloc`
+//! library for loaders and sandboxes.
+
+mod cursor;
+mod ehdr;
+mod shdr;
+mod sym;
+
+pub use cursor::{ByteCursor, ParseError};
+pub use ehdr::{Elf32Ehdr, Elf64Ehdr, ObjectType};
+pub use shdr::{read_section_headers, shstrndx, Elf32Shdr, Elf64Shdr};
+pub use sym::{Elf32Sym, Elf64Sym, SymbolBinding, SymbolVisibility};
+
+/// `e_ident[EI_CLASS]` value of a 64-bit object
+const ELFCLASS64: u8 = 2;
+/// `e_ident[EI_DATA]` value of a big-endian object
+const ELFDATA2MSB: u8 = 2;
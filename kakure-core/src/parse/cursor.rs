@@ -0,0 +1,134 @@
+use core::fmt;
+
+/// Why a structure could not be decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// `wanted` more bytes were needed at `offset`, past the end of the input
+    UnexpectedEof { offset: usize, wanted: usize },
+    /// The input does not start with `\x7fELF`
+    BadMagic,
+    /// A table is not a whole number of `entry_size`-byte records
+    BadTableSize { size: usize, entry_size: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEof { offset, wanted } => {
+                write!(
+                    f,
+                    "Unexpected end of input reading {} bytes at {:#x}",
+                    wanted, offset
+                )
+            }
+            ParseError::BadMagic => write!(f, "Not an ELF file (bad magic)"),
+            ParseError::BadTableSize { size, entry_size } => write!(
+                f,
+                "Table of {} bytes is not a multiple of its {}-byte entries",
+                size, entry_size
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Reads fixed-width integers from a byte slice in a chosen byte order
+///
+/// The `no_std` stand-in for `std::io::Cursor` plus `byteorder`: reads past the end
+/// fail with [`ParseError::UnexpectedEof`] instead of an I/O error.
+#[derive(Debug, Clone)]
+pub struct ByteCursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> ByteCursor<'a> {
+    /// A little-endian cursor at the start of `data`
+    pub fn new(data: &'a [u8]) -> Self {
+        Self::with_endian(data, true)
+    }
+
+    pub fn with_endian(data: &'a [u8], little_endian: bool) -> Self {
+        Self {
+            data,
+            pos: 0,
+            little_endian,
+        }
+    }
+
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+
+    pub fn set_little_endian(&mut self, little_endian: bool) {
+        self.little_endian = little_endian;
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Move to absolute offset `pos`, which may be the end of the input but not past it
+    pub fn seek(&mut self, pos: usize) -> Result<(), ParseError> {
+        if pos > self.data.len() {
+            return Err(ParseError::UnexpectedEof {
+                offset: self.data.len(),
+                wanted: pos - self.data.len(),
+            });
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    /// The next `len` bytes, borrowed from the input
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.pos..end))
+            .ok_or(ParseError::UnexpectedEof {
+                offset: self.pos,
+                wanted: len,
+            })?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, ParseError> {
+        let bytes = self.read_array()?;
+        Ok(if self.little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ParseError> {
+        let bytes = self.read_array()?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, ParseError> {
+        let bytes = self.read_array()?;
+        Ok(if self.little_endian {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+}
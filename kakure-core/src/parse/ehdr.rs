@@ -0,0 +1,253 @@
+use super::cursor::{ByteCursor, ParseError};
+use super::ELFDATA2MSB;
+
+const ELFMAG: &[u8; 4] = b"\x7fELF";
+const EI_DATA: usize = 5;
+
+/// Read `e_ident`, check the magic and switch `cur` to the byte order it declares
+fn read_ident(cur: &mut ByteCursor<'_>) -> Result<[u8; 16], ParseError> {
+    let e_ident: [u8; 16] = cur.read_array()?;
+    if !e_ident.starts_with(ELFMAG) {
+        return Err(ParseError::BadMagic);
+    }
+    cur.set_little_endian(e_ident[EI_DATA] != ELFDATA2MSB);
+    Ok(e_ident)
+}
+
+/// Kind of object file, decoded from `e_type`
+///
+/// Note that position-independent executables are `Dyn`, like shared libraries;
+/// only fixed-address executables are `Exec`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectType {
+    /// `ET_NONE`, or a value outside the ranges below
+    None,
+    /// `ET_REL`: relocatable object (`.o`)
+    Rel,
+    /// `ET_EXEC`: executable linked at a fixed address
+    Exec,
+    /// `ET_DYN`: shared object or position-independent executable
+    Dyn,
+    /// `ET_CORE`: core dump
+    Core,
+    /// `ET_LOPROC..=ET_HIPROC`: processor-specific type
+    ProcSpecific(u16),
+}
+
+impl ObjectType {
+    pub fn from_e_type(e_type: u16) -> Self {
+        match e_type {
+            1 => ObjectType::Rel,
+            2 => ObjectType::Exec,
+            3 => ObjectType::Dyn,
+            4 => ObjectType::Core,
+            0xff00..=0xffff => ObjectType::ProcSpecific(e_type),
+            _ => ObjectType::None,
+        }
+    }
+
+    /// Human-readable name, e.g. "executable" or "shared object"
+    pub fn name(self) -> &'static str {
+        match self {
+            ObjectType::None => "unknown",
+            ObjectType::Rel => "relocatable",
+            ObjectType::Exec => "executable",
+            ObjectType::Dyn => "shared object",
+            ObjectType::Core => "core",
+            ObjectType::ProcSpecific(_) => "processor-specific",
+        }
+    }
+}
+
+impl core::fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Represents the ELF (Executable and Linkable Format) header for a 64-bit object file.
+///
+/// This structure corresponds to the standard `Elf64_Ehdr` defined in the ELF specification.
+/// It appears at the very beginning of every ELF file and contains metadata describing
+/// the file’s organization and layout.
+///
+/// Reference: [ELF Specification v1.2](https://refspecs.linuxfoundation.org/elf/elf.pdf)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elf64Ehdr {
+    /// ELF identification bytes (magic number and other information).
+    ///
+    /// The first 4 bytes should be `0x7F`, `'E'`, `'L'`, `'F'`.
+    /// Remaining bytes encode class (32/64-bit), endianness, and version.
+    pub e_ident: [u8; 16],
+
+    /// Object file type (e.g. relocatable, executable, shared, core).
+    ///
+    /// Common values:
+    /// - `ET_NONE` (0): No file type
+    /// - `ET_REL` (1): Relocatable file
+    /// - `ET_EXEC` (2): Executable file
+    /// - `ET_DYN` (3): Shared object
+    /// - `ET_CORE` (4): Core dump
+    pub e_type: u16,
+
+    /// Target architecture (e.g., x86_64, ARM).
+    ///
+    /// Common values:
+    /// - `EM_X86_64` (62)
+    /// - `EM_AARCH64` (183)
+    pub e_machine: u16,
+
+    /// ELF version (usually set to `EV_CURRENT` = 1).
+    pub e_version: u32,
+
+    /// Virtual address of the program entry point.
+    ///
+    /// This is where execution starts when the ELF is loaded.
+    pub e_entry: u64,
+
+    /// File offset of the program header table.
+    ///
+    /// Points to an array of `Elf64Phdr` entries.
+    pub e_phoff: u64,
+
+    /// File offset of the section header table.
+    ///
+    /// Points to an array of `Elf64Shdr` entries.
+    pub e_shoff: u64,
+
+    /// Processor-specific flags.
+    pub e_flags: u32,
+
+    /// Size of this ELF header (usually `64` bytes for ELF64).
+    pub e_ehsize: u16,
+
+    /// Size of one entry in the program header table.
+    pub e_phentsize: u16,
+
+    /// Number of entries in the program header table.
+    pub e_phnum: u16,
+
+    /// Size of one entry in the section header table.
+    pub e_shentsize: u16,
+
+    /// Number of entries in the section header table.
+    pub e_shnum: u16,
+
+    /// Index of the section header string table.
+    ///
+    /// This section contains the names of all other sections.
+    pub e_shstrndx: u16,
+}
+
+impl Elf64Ehdr {
+    /// Size of `Elf64_Ehdr` in the file
+    pub const SIZE: usize = 64;
+
+    /// `e_type` as an [`ObjectType`]
+    pub fn object_type(&self) -> ObjectType {
+        ObjectType::from_e_type(self.e_type)
+    }
+
+    /// Decode the header at the cursor, in the byte order given by `e_ident`
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Elf64Ehdr {
+            e_ident: read_ident(cur)?,
+            e_type: cur.read_u16()?,
+            e_machine: cur.read_u16()?,
+            e_version: cur.read_u32()?,
+            e_entry: cur.read_u64()?,
+            e_phoff: cur.read_u64()?,
+            e_shoff: cur.read_u64()?,
+            e_flags: cur.read_u32()?,
+            e_ehsize: cur.read_u16()?,
+            e_phentsize: cur.read_u16()?,
+            e_phnum: cur.read_u16()?,
+            e_shentsize: cur.read_u16()?,
+            e_shnum: cur.read_u16()?,
+            e_shstrndx: cur.read_u16()?,
+        })
+    }
+}
+
+/// Represents the ELF header for a 32-bit object file.
+///
+/// This structure corresponds to `Elf32_Ehdr`. The layout matches [`Elf64Ehdr`]
+/// except that addresses and offsets (`e_entry`, `e_phoff`, `e_shoff`) are 32 bits wide.
+///
+/// Reference: [ELF Specification v1.2](https://refspecs.linuxfoundation.org/elf/elf.pdf)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elf32Ehdr {
+    /// ELF identification bytes; `e_ident[4]` is `ELFCLASS32` (1).
+    pub e_ident: [u8; 16],
+
+    /// Object file type (see [`Elf64Ehdr::e_type`]).
+    pub e_type: u16,
+
+    /// Target architecture (e.g. `EM_386` (3), `EM_ARM` (40)).
+    pub e_machine: u16,
+
+    /// ELF version (usually set to `EV_CURRENT` = 1).
+    pub e_version: u32,
+
+    /// Virtual address of the program entry point.
+    pub e_entry: u32,
+
+    /// File offset of the program header table.
+    pub e_phoff: u32,
+
+    /// File offset of the section header table.
+    pub e_shoff: u32,
+
+    /// Processor-specific flags.
+    pub e_flags: u32,
+
+    /// Size of this ELF header (usually `52` bytes for ELF32).
+    pub e_ehsize: u16,
+
+    /// Size of one entry in the program header table.
+    pub e_phentsize: u16,
+
+    /// Number of entries in the program header table.
+    pub e_phnum: u16,
+
+    /// Size of one entry in the section header table.
+    pub e_shentsize: u16,
+
+    /// Number of entries in the section header table.
+    pub e_shnum: u16,
+
+    /// Index of the section header string table.
+    pub e_shstrndx: u16,
+}
+
+impl Elf32Ehdr {
+    /// Size of `Elf32_Ehdr` in the file
+    pub const SIZE: usize = 52;
+
+    /// `e_type` as an [`ObjectType`]
+    pub fn object_type(&self) -> ObjectType {
+        ObjectType::from_e_type(self.e_type)
+    }
+
+    /// Decode the header at the cursor, in the byte order given by `e_ident`
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Elf32Ehdr {
+            e_ident: read_ident(cur)?,
+            e_type: cur.read_u16()?,
+            e_machine: cur.read_u16()?,
+            e_version: cur.read_u32()?,
+            e_entry: cur.read_u32()?,
+            e_phoff: cur.read_u32()?,
+            e_shoff: cur.read_u32()?,
+            e_flags: cur.read_u32()?,
+            e_ehsize: cur.read_u16()?,
+            e_phentsize: cur.read_u16()?,
+            e_phnum: cur.read_u16()?,
+            e_shentsize: cur.read_u16()?,
+            e_shnum: cur.read_u16()?,
+            e_shstrndx: cur.read_u16()?,
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use super::cursor::{ByteCursor, ParseError};
+use super::ehdr::{Elf32Ehdr, Elf64Ehdr};
+use super::ELFCLASS64;
+use alloc::vec::Vec;
+
+/// `SHT_NOBITS`: the section occupies no space in the file (`.bss`, `.tbss`)
+const SHT_NOBITS: u32 = 8;
+/// `e_shstrndx` value meaning the real index is in section 0's `sh_link`
+const SHN_XINDEX: u16 = 0xffff;
+
+/// A 64-bit ELF section header (`Elf64_Shdr`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elf64Shdr {
+    /// Offset of the section name in the section header string table
+    pub sh_name: u32,
+    pub sh_type: u32,
+    pub sh_flags: u64,
+    /// Address of the section once loaded, 0 if it is not allocated
+    pub sh_addr: u64,
+    pub sh_offset: u64,
+    pub sh_size: u64,
+    pub sh_link: u32,
+    pub sh_info: u32,
+    pub sh_addralign: u64,
+    pub sh_entsize: u64,
+}
+
+impl Elf64Shdr {
+    /// Size of one `Elf64_Shdr` in the file
+    pub const SIZE: usize = 64;
+
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            sh_name: cur.read_u32()?,
+            sh_type: cur.read_u32()?,
+            sh_flags: cur.read_u64()?,
+            sh_addr: cur.read_u64()?,
+            sh_offset: cur.read_u64()?,
+            sh_size: cur.read_u64()?,
+            sh_link: cur.read_u32()?,
+            sh_info: cur.read_u32()?,
+            sh_addralign: cur.read_u64()?,
+            sh_entsize: cur.read_u64()?,
+        })
+    }
+
+    /// The section's name as stored in `shstrtab`, possibly empty
+    pub fn name<'a>(&self, shstrtab: &'a [u8]) -> &'a str {
+        let Some(tail) = shstrtab.get(self.sh_name as usize..) else {
+            return "<invalid_name>";
+        };
+        let len = tail.iter().position(|&b| b == 0).unwrap_or(tail.len());
+        core::str::from_utf8(&tail[..len]).unwrap_or("<invalid_utf8>")
+    }
+
+    /// The section's bytes within `file`, or `None` for `SHT_NOBITS` and out-of-range sections
+    pub fn data<'a>(&self, file: &'a [u8]) -> Option<&'a [u8]> {
+        if self.sh_type == SHT_NOBITS {
+            return None;
+        }
+        let start = usize::try_from(self.sh_offset).ok()?;
+        let end = start.checked_add(usize::try_from(self.sh_size).ok()?)?;
+        file.get(start..end)
+    }
+}
+
+/// A 32-bit ELF section header (`Elf32_Shdr`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elf32Shdr {
+    pub sh_name: u32,
+    pub sh_type: u32,
+    pub sh_flags: u32,
+    pub sh_addr: u32,
+    pub sh_offset: u32,
+    pub sh_size: u32,
+    pub sh_link: u32,
+    pub sh_info: u32,
+    pub sh_addralign: u32,
+    pub sh_entsize: u32,
+}
+
+impl Elf32Shdr {
+    /// Size of one `Elf32_Shdr` in the file
+    pub const SIZE: usize = 40;
+
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            sh_name: cur.read_u32()?,
+            sh_type: cur.read_u32()?,
+            sh_flags: cur.read_u32()?,
+            sh_addr: cur.read_u32()?,
+            sh_offset: cur.read_u32()?,
+            sh_size: cur.read_u32()?,
+            sh_link: cur.read_u32()?,
+            sh_info: cur.read_u32()?,
+            sh_addralign: cur.read_u32()?,
+            sh_entsize: cur.read_u32()?,
+        })
+    }
+}
+
+/// Widen a 32-bit section header so both classes can be handled alike
+impl From<Elf32Shdr> for Elf64Shdr {
+    fn from(sh: Elf32Shdr) -> Self {
+        Self {
+            sh_name: sh.sh_name,
+            sh_type: sh.sh_type,
+            sh_flags: sh.sh_flags as u64,
+            sh_addr: sh.sh_addr as u64,
+            sh_offset: sh.sh_offset as u64,
+            sh_size: sh.sh_size as u64,
+            sh_link: sh.sh_link,
+            sh_info: sh.sh_info,
+            sh_addralign: sh.sh_addralign as u64,
+            sh_entsize: sh.sh_entsize as u64,
+        }
+    }
+}
+
+/// Read the section header table of an ELF file of either class
+///
+/// 32-bit headers are widened to [`Elf64Shdr`]. A file without a section header
+/// table yields an empty list. Files with 0xff00 or more sections store 0 in
+/// `e_shnum`; the real count is then taken from section 0's `sh_size`.
+pub fn read_section_headers(file: &[u8]) -> Result<Vec<Elf64Shdr>, ParseError> {
+    let mut cur = ByteCursor::new(file);
+    let is_64 = file.get(4) == Some(&ELFCLASS64);
+    let (shoff, shnum) = if is_64 {
+        let ehdr = Elf64Ehdr::parse(&mut cur)?;
+        (ehdr.e_shoff, ehdr.e_shnum)
+    } else {
+        let ehdr = Elf32Ehdr::parse(&mut cur)?;
+        (ehdr.e_shoff as u64, ehdr.e_shnum)
+    };
+    if shoff == 0 {
+        return Ok(Vec::new());
+    }
+
+    cur.seek(usize::try_from(shoff).unwrap_or(usize::MAX))?;
+    let mut next = || {
+        if is_64 {
+            Elf64Shdr::parse(&mut cur)
+        } else {
+            Elf32Shdr::parse(&mut cur).map(Elf64Shdr::from)
+        }
+    };
+    let first = next()?;
+    let count = match shnum {
+        0 => first.sh_size,
+        n => n as u64,
+    };
+    // Every header takes at least 40 bytes, so a count the file cannot hold is bogus
+    if count > (file.len() / Elf32Shdr::SIZE) as u64 {
+        return Err(ParseError::UnexpectedEof {
+            offset: cur.position(),
+            wanted: (count as usize).saturating_mul(Elf32Shdr::SIZE),
+        });
+    }
+
+    let mut headers = Vec::with_capacity(count.max(1) as usize);
+    headers.push(first);
+    for _ in 1..count {
+        headers.push(next()?);
+    }
+    Ok(headers)
+}
+
+/// Index of the section header string table
+///
+/// When the index does not fit in `e_shstrndx` it holds `SHN_XINDEX` (0xffff) and
+/// the real index is in section 0's `sh_link`.
+pub fn shstrndx(e_shstrndx: u16, sections: &[Elf64Shdr]) -> usize {
+    match e_shstrndx {
+        SHN_XINDEX => sections.first().map_or(0, |sh| sh.sh_link as usize),
+        index => index as usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{Elf32Sym, Elf64Sym, SymbolBinding};
+
+    #[test]
+    fn reads_the_32_bit_fixture_without_std_io() {
+        let file = include_bytes!("../../tests/fixtures/x86_32_symtab.elf");
+        let sections = read_section_headers(file).unwrap();
+        let shstrtab = sections[5].data(file).unwrap();
+        let names: Vec<_> = sections.iter().map(|sh| sh.name(shstrtab)).collect();
+        assert_eq!(
+            names,
+            ["", ".text", ".data", ".symtab", ".strtab", ".shstrtab"]
+        );
+        assert_eq!(sections[1].sh_addr, 0x8048054);
+
+        let symbols = Elf32Sym::from_section(sections[3].data(file).unwrap()).unwrap();
+        let strtab = sections[4].data(file).unwrap();
+        let helper = symbols
+            .iter()
+            .find(|sym| Elf64Sym::from(**sym).raw_name(strtab) == "helper")
+            .unwrap();
+        assert_eq!((helper.st_value, helper.st_size), (0x804805e, 6));
+        assert_eq!(Elf64Sym::from(*helper).binding(), SymbolBinding::Global);
+    }
+
+    /// Build an ELF64 whose header uses the overflow sentinels for both fields
+    fn extended_numbering_elf() -> Vec<u8> {
+        let shstrtab = b"\0.text\0.shstrtab\0";
+        let mut file = vec![0u8; 64];
+        file[..4].copy_from_slice(b"\x7fELF");
+        file[4] = 2; // ELFCLASS64
+        file[5] = 1; // ELFDATA2LSB
+        file[0x28..0x30].copy_from_slice(&0x80u64.to_le_bytes()); // e_shoff
+        file[0x3a..0x3c].copy_from_slice(&64u16.to_le_bytes()); // e_shentsize
+        file[0x3c..0x3e].copy_from_slice(&0u16.to_le_bytes()); // e_shnum
+        file[0x3e..0x40].copy_from_slice(&0xffffu16.to_le_bytes()); // e_shstrndx
+        file.extend_from_slice(shstrtab);
+        file.resize(0x80, 0);
+
+        let mut section = |name: u32, ty: u32, offset: u64, size: u64, link: u32| {
+            file.extend_from_slice(&name.to_le_bytes());
+            file.extend_from_slice(&ty.to_le_bytes());
+            file.extend_from_slice(&[0; 16]);
+            file.extend_from_slice(&offset.to_le_bytes());
+            file.extend_from_slice(&size.to_le_bytes());
+            file.extend_from_slice(&link.to_le_bytes());
+            file.extend_from_slice(&[0; 20]);
+        };
+        section(0, 0, 0, 3, 2); // null: real count 3, real shstrndx 2
+        section(1, 1, 0, 0, 0);
+        section(7, 3, 64, shstrtab.len() as u64, 0);
+        file
+    }
+
+    #[test]
+    fn extended_section_numbering_is_followed() {
+        let file = extended_numbering_elf();
+        let sections = read_section_headers(&file).unwrap();
+        assert_eq!(sections.len(), 3);
+
+        let shstrtab = sections[shstrndx(0xffff, &sections)].data(&file).unwrap();
+        assert_eq!(sections[1].name(shstrtab), ".text");
+    }
+}
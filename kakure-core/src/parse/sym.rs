@@ -0,0 +1,311 @@
+use super::cursor::{ByteCursor, ParseError};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// `SHN_UNDEF`: the symbol is referenced here but defined elsewhere
+const SHN_UNDEF: u16 = 0;
+/// `STT_TLS`: the value is an offset into the thread-local storage block
+const STT_TLS: u8 = 6;
+
+/// Symbol visibility, encoded in the low two bits of `st_other`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolVisibility {
+    /// `STV_DEFAULT`: visibility follows the symbol's binding
+    Default,
+    /// `STV_INTERNAL`: processor-specific hidden class
+    Internal,
+    /// `STV_HIDDEN`: not visible outside the component that defines it
+    Hidden,
+    /// `STV_PROTECTED`: visible, but not preemptible
+    Protected,
+}
+
+impl SymbolVisibility {
+    pub fn from_st_other(st_other: u8) -> Self {
+        match st_other & 0x3 {
+            1 => SymbolVisibility::Internal,
+            2 => SymbolVisibility::Hidden,
+            3 => SymbolVisibility::Protected,
+            _ => SymbolVisibility::Default,
+        }
+    }
+}
+
+impl core::str::FromStr for SymbolVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "DEFAULT" => Ok(SymbolVisibility::Default),
+            "INTERNAL" => Ok(SymbolVisibility::Internal),
+            "HIDDEN" => Ok(SymbolVisibility::Hidden),
+            "PROTECTED" => Ok(SymbolVisibility::Protected),
+            _ => Err(format!("Unknown symbol visibility: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for SymbolVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SymbolVisibility::Default => "DEFAULT",
+            SymbolVisibility::Internal => "INTERNAL",
+            SymbolVisibility::Hidden => "HIDDEN",
+            SymbolVisibility::Protected => "PROTECTED",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Symbol binding, encoded in the high nibble of `st_info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolBinding {
+    /// `STB_LOCAL`: not visible outside the object file (`static` functions)
+    Local,
+    /// `STB_GLOBAL`: visible to every object being combined
+    Global,
+    /// `STB_WEAK`: global, but may be overridden by a global definition
+    Weak,
+    /// `STB_GNU_UNIQUE`: global, and unique across the whole process
+    Unique,
+}
+
+impl SymbolBinding {
+    /// OS- and processor-specific bindings other than `STB_GNU_UNIQUE` are treated as global
+    pub fn from_st_info(st_info: u8) -> Self {
+        match st_info >> 4 {
+            0 => SymbolBinding::Local,
+            2 => SymbolBinding::Weak,
+            10 => SymbolBinding::Unique,
+            _ => SymbolBinding::Global,
+        }
+    }
+
+    pub fn is_local(self) -> bool {
+        self == SymbolBinding::Local
+    }
+}
+
+impl core::str::FromStr for SymbolBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "LOCAL" => Ok(SymbolBinding::Local),
+            "GLOBAL" => Ok(SymbolBinding::Global),
+            "WEAK" => Ok(SymbolBinding::Weak),
+            "UNIQUE" => Ok(SymbolBinding::Unique),
+            _ => Err(format!("Unknown symbol binding: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for SymbolBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SymbolBinding::Local => "LOCAL",
+            SymbolBinding::Global => "GLOBAL",
+            SymbolBinding::Weak => "WEAK",
+            SymbolBinding::Unique => "UNIQUE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Elf64Sym {
+    pub st_name: u32,
+    pub st_info: u8,
+    pub st_other: u8,
+    pub st_shndx: u16,
+    pub st_value: u64,
+    pub st_size: u64,
+}
+
+impl Elf64Sym {
+    /// Size of one `Elf64_Sym` record in the file
+    pub const SIZE: usize = 24;
+
+    /// Parse a little-endian `.symtab`/`.dynsym`, see [`Elf64Sym::from_section_endian`]
+    pub fn from_section(symtab_data: &[u8]) -> Result<Vec<Elf64Sym>, ParseError> {
+        Self::from_section_endian(symtab_data, true)
+    }
+
+    /// Parse every record in a symbol table section
+    ///
+    /// Undefined symbols and symbols with a zero value or size are skipped, except
+    /// that TLS symbols may have a zero value (the first variable in the TLS block).
+    pub fn from_section_endian(
+        symtab_data: &[u8],
+        little_endian: bool,
+    ) -> Result<Vec<Elf64Sym>, ParseError> {
+        let symbols = Self::all_from_section_endian(symtab_data, little_endian)?
+            .into_iter()
+            .filter(|sym| {
+                sym.st_shndx != SHN_UNDEF
+                    && (sym.st_value != 0 || sym.sym_type() == STT_TLS)
+                    && sym.st_size != 0
+            })
+            .collect();
+        Ok(symbols)
+    }
+
+    /// Parse every record in a symbol table section, undefined and null ones included
+    pub fn all_from_section_endian(
+        symtab_data: &[u8],
+        little_endian: bool,
+    ) -> Result<Vec<Elf64Sym>, ParseError> {
+        if !symtab_data.len().is_multiple_of(Self::SIZE) {
+            return Err(ParseError::BadTableSize {
+                size: symtab_data.len(),
+                entry_size: Self::SIZE,
+            });
+        }
+
+        let mut cur = ByteCursor::with_endian(symtab_data, little_endian);
+        (0..symtab_data.len() / Self::SIZE)
+            .map(|_| Self::parse(&mut cur))
+            .collect()
+    }
+
+    /// Decode one 24-byte record at the cursor
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            st_name: cur.read_u32()?,
+            st_info: cur.read_u8()?,
+            st_other: cur.read_u8()?,
+            st_shndx: cur.read_u16()?,
+            st_value: cur.read_u64()?,
+            st_size: cur.read_u64()?,
+        })
+    }
+
+    pub fn visibility(&self) -> SymbolVisibility {
+        SymbolVisibility::from_st_other(self.st_other)
+    }
+
+    pub fn binding(&self) -> SymbolBinding {
+        SymbolBinding::from_st_info(self.st_info)
+    }
+
+    /// Symbol type (`STT_*`), the low nibble of `st_info`
+    pub fn sym_type(&self) -> u8 {
+        self.st_info & 0xf
+    }
+
+    /// Whether the symbol is only referenced here (`SHN_UNDEF`), to be defined elsewhere
+    pub fn is_undefined(&self) -> bool {
+        self.st_shndx == SHN_UNDEF
+    }
+
+    /// The symbol's name as stored in `strtab_data`, possibly empty
+    pub fn raw_name<'a>(&self, strtab_data: &'a [u8]) -> &'a str {
+        let Some(tail) = strtab_data.get(self.st_name as usize..) else {
+            return "<invalid_name>";
+        };
+        let len = tail.iter().position(|&b| b == 0).unwrap_or(tail.len());
+        core::str::from_utf8(&tail[..len]).unwrap_or("<invalid_utf8>")
+    }
+
+    pub fn name_from_symtab(&self, strtab_data: &[u8]) -> Result<String, ParseError> {
+        let name = self.raw_name(strtab_data);
+
+        let function_identifier = if name.is_empty() {
+            format!("FUNC_{:#x}", self.st_value)
+        } else {
+            name.to_string()
+        };
+
+        Ok(function_identifier)
+    }
+}
+
+/// A 32-bit ELF symbol (`Elf32_Sym`)
+///
+/// Unlike `Elf64_Sym`, the value and size come before `st_info`/`st_other`/`st_shndx`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Elf32Sym {
+    pub st_name: u32,
+    pub st_value: u32,
+    pub st_size: u32,
+    pub st_info: u8,
+    pub st_other: u8,
+    pub st_shndx: u16,
+}
+
+impl Elf32Sym {
+    /// Size of one `Elf32_Sym` record in the file
+    pub const SIZE: usize = 16;
+
+    /// Parse a little-endian `.symtab`/`.dynsym`, see [`Elf32Sym::from_section_endian`]
+    pub fn from_section(symtab_data: &[u8]) -> Result<Vec<Elf32Sym>, ParseError> {
+        Self::from_section_endian(symtab_data, true)
+    }
+
+    /// Parse every record in a 32-bit symbol table section
+    ///
+    /// Undefined symbols and symbols with a zero value or size are skipped, except
+    /// that TLS symbols may have a zero value (the first variable in the TLS block).
+    pub fn from_section_endian(
+        symtab_data: &[u8],
+        little_endian: bool,
+    ) -> Result<Vec<Elf32Sym>, ParseError> {
+        let symbols = Self::all_from_section_endian(symtab_data, little_endian)?
+            .into_iter()
+            .filter(|sym| {
+                sym.st_shndx != SHN_UNDEF
+                    && (sym.st_value != 0 || sym.st_info & 0xf == STT_TLS)
+                    && sym.st_size != 0
+            })
+            .collect();
+        Ok(symbols)
+    }
+
+    /// Parse every record in a 32-bit symbol table section, undefined and null ones included
+    pub fn all_from_section_endian(
+        symtab_data: &[u8],
+        little_endian: bool,
+    ) -> Result<Vec<Elf32Sym>, ParseError> {
+        if !symtab_data.len().is_multiple_of(Self::SIZE) {
+            return Err(ParseError::BadTableSize {
+                size: symtab_data.len(),
+                entry_size: Self::SIZE,
+            });
+        }
+
+        let mut cur = ByteCursor::with_endian(symtab_data, little_endian);
+        (0..symtab_data.len() / Self::SIZE)
+            .map(|_| Self::parse(&mut cur))
+            .collect()
+    }
+
+    /// Decode one 16-byte record at the cursor
+    pub fn parse(cur: &mut ByteCursor<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            st_name: cur.read_u32()?,
+            st_value: cur.read_u32()?,
+            st_size: cur.read_u32()?,
+            st_info: cur.read_u8()?,
+            st_other: cur.read_u8()?,
+            st_shndx: cur.read_u16()?,
+        })
+    }
+}
+
+/// Widen a 32-bit symbol so it can go through the same parsers as 64-bit ones
+impl From<Elf32Sym> for Elf64Sym {
+    fn from(sym: Elf32Sym) -> Self {
+        Self {
+            st_name: sym.st_name,
+            st_info: sym.st_info,
+            st_other: sym.st_other,
+            st_shndx: sym.st_shndx,
+            st_value: sym.st_value as u64,
+            st_size: sym.st_size as u64,
+        }
+    }
+}
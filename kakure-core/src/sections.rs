@@ -1,12 +1,21 @@
 use std::io::{self, SeekFrom};
+use std::ops::Range;
 
+use crate::DigestAlgorithm;
+
+use goblin::elf::program_header::{PF_R, PF_W, PF_X};
+use goblin::elf::section_header::{SHF_ALLOC, SHF_EXECINSTR, SHF_WRITE, SHT_NOBITS, SHT_NULL};
 use goblin::elf::{Elf, SectionHeader};
 use goblin::elf32::program_header::PT_LOAD;
+use goblin::mach::constants::{S_ATTR_PURE_INSTRUCTIONS, S_ATTR_SOME_INSTRUCTIONS, VM_PROT_WRITE};
+use goblin::mach::MachO;
 
 #[derive(Debug)]
 pub enum PlatformType<T> {
     ELF(T),
     PE(T),
+    MachO(T),
+    Wasm(T),
     Unknown(T),
 }
 
@@ -22,15 +31,92 @@ pub struct KSection {
 
 impl KSection {
     pub fn raw_len(&self) -> usize {
-        match &self.raw_data {
-            PlatformType::ELF(b) | PlatformType::PE(b) | PlatformType::Unknown(b) => b.len(),
-        }
+        self.raw_data().len()
     }
 
     pub fn raw_data(&self) -> &Vec<u8> {
         match &self.raw_data {
-            PlatformType::ELF(b) | PlatformType::PE(b) | PlatformType::Unknown(b) => &b,
+            PlatformType::ELF(b)
+            | PlatformType::PE(b)
+            | PlatformType::MachO(b)
+            | PlatformType::Wasm(b)
+            | PlatformType::Unknown(b) => b,
+        }
+    }
+
+    /// Where the section's bytes sit in the file
+    ///
+    /// Spans the bytes actually read, so it is empty for `SHT_NOBITS` sections
+    /// such as `.bss`, whatever their `size`.
+    pub fn file_range(&self) -> Range<u64> {
+        self.file_offset..self.file_offset + self.raw_len() as u64
+    }
+
+    /// The addresses the section occupies once loaded, `vma..vma + size`
+    ///
+    /// Meaningful for allocated sections only; the end saturates at `u64::MAX`.
+    pub fn vma_range(&self) -> Range<u64> {
+        self.vma..self.vma.saturating_add(self.size)
+    }
+
+    /// Returns true if the section holds executable code (`SHF_EXECINSTR`).
+    pub fn is_executable(&self) -> bool {
+        self.flags & SHF_EXECINSTR as u64 != 0
+    }
+
+    /// Returns true if the section is writable at runtime (`SHF_WRITE`).
+    pub fn is_writable(&self) -> bool {
+        self.flags & SHF_WRITE as u64 != 0
+    }
+
+    /// Returns true if the section occupies memory in the loaded image (`SHF_ALLOC`).
+    pub fn is_alloc(&self) -> bool {
+        self.flags & SHF_ALLOC as u64 != 0
+    }
+
+    /// Returns the flags in `readelf -S` style, e.g. `"AX"` or `"WA"`.
+    pub fn flags_string(&self) -> String {
+        let mut flags = String::new();
+        if self.is_writable() {
+            flags.push('W');
+        }
+        if self.is_alloc() {
+            flags.push('A');
+        }
+        if self.is_executable() {
+            flags.push('X');
         }
+        flags
+    }
+
+    /// Hex digest of the section's file bytes
+    pub fn digest(&self, algo: DigestAlgorithm) -> String {
+        algo.hex_digest(self.raw_data())
+    }
+
+    /// Shannon entropy of the section's file bytes, in bits per byte (0.0 to 8.0)
+    ///
+    /// Compressed or encrypted data sits close to 8.0; ordinary code is usually
+    /// between 5.0 and 6.5. Empty sections have an entropy of 0.0.
+    pub fn entropy(&self) -> f64 {
+        let data = self.raw_data();
+        if data.is_empty() {
+            return 0.0;
+        }
+
+        let mut counts = [0usize; 256];
+        for &byte in data {
+            counts[byte as usize] += 1;
+        }
+        let len = data.len() as f64;
+        counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / len;
+                -p * p.log2()
+            })
+            .sum()
     }
 
     pub fn from_goblin_sh<R: io::Seek + io::Read>(
@@ -39,7 +125,40 @@ impl KSection {
         elf: &Elf,
     ) -> io::Result<Self> {
         let name = elf.shdr_strtab.get_at(sh.sh_name).unwrap_or("").to_string();
-        let mut raw = vec![0u8; sh.sh_size as usize];
+        // The null section describes nothing; with extended section numbering its
+        // sh_size/sh_link hold the real section count and string table index
+        if sh.sh_type == SHT_NULL {
+            return Ok(KSection {
+                name,
+                vma: 0,
+                size: 0,
+                file_offset: 0,
+                flags: 0,
+                raw_data: PlatformType::ELF(Vec::new()),
+            });
+        }
+        // NOBITS sections (.bss, .tbss) occupy no bytes in the file
+        let file_size = if sh.sh_type == SHT_NOBITS {
+            0
+        } else {
+            sh.sh_size
+        };
+        // Checked before allocating, so a corrupt sh_size can't request a huge buffer
+        let file_len = cursor.seek(SeekFrom::End(0))?;
+        if sh
+            .sh_offset
+            .checked_add(file_size)
+            .is_none_or(|end| end > file_len)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Section {} [{:#x}, +{:#x}) lies outside the file",
+                    name, sh.sh_offset, file_size
+                ),
+            ));
+        }
+        let mut raw = vec![0u8; file_size as usize];
         cursor.seek(SeekFrom::Start(sh.sh_offset))?;
         cursor.read_exact(&mut raw)?;
 
@@ -53,6 +172,120 @@ impl KSection {
         })
     }
 
+    /// Translate segment `PF_*` permissions into the `SHF_*` bits used by real sections
+    fn segment_flags_to_shf(p_flags: u32) -> u64 {
+        let mut flags = SHF_ALLOC as u64;
+        if p_flags & PF_X != 0 {
+            flags |= SHF_EXECINSTR as u64;
+        }
+        if p_flags & PF_W != 0 {
+            flags |= SHF_WRITE as u64;
+        }
+        flags
+    }
+
+    /// Name a `PT_LOAD` segment by its permissions, e.g. `.segment_rx` or `.segment_ro`
+    ///
+    /// The segment holding the entry point gets an `_entry` suffix, and the program
+    /// header index is appended when the name would otherwise repeat.
+    fn segment_name(p_flags: u32, contains_entry: bool, index: usize, taken: &[Self]) -> String {
+        let mut perms = String::new();
+        if p_flags & PF_R != 0 {
+            perms.push('r');
+        }
+        if p_flags & PF_W != 0 {
+            perms.push('w');
+        }
+        if p_flags & PF_X != 0 {
+            perms.push('x');
+        }
+        if perms == "r" {
+            perms = "ro".to_string();
+        }
+
+        let mut name = format!(".segment_{}", perms);
+        if contains_entry {
+            name.push_str("_entry");
+        }
+        if taken.iter().any(|s| s.name == name) {
+            name = format!("{}_{}", name, index);
+        }
+        name
+    }
+
+    /// Build one section per Mach-O section, named `SEGMENT,section` (e.g. `__TEXT,__text`)
+    ///
+    /// Flags are translated into the `SHF_*` bits used for ELF so the predicates
+    /// behave the same across formats.
+    pub fn from_goblin_macho(macho: &MachO) -> goblin::error::Result<Vec<Self>> {
+        let mut sections = vec![];
+        for segment in macho.segments.iter() {
+            for (section, data) in segment.sections()? {
+                let mut flags = SHF_ALLOC as u64;
+                if section.flags & (S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS) != 0 {
+                    flags |= SHF_EXECINSTR as u64;
+                }
+                if segment.initprot & VM_PROT_WRITE != 0 {
+                    flags |= SHF_WRITE as u64;
+                }
+
+                sections.push(KSection {
+                    name: format!("{},{}", section.segname()?, section.name()?),
+                    vma: section.addr,
+                    size: section.size,
+                    file_offset: section.offset as u64,
+                    flags,
+                    raw_data: PlatformType::MachO(data.to_vec()),
+                });
+            }
+        }
+        Ok(sections)
+    }
+
+    /// Build one section per WebAssembly section, each placed at its file offset
+    ///
+    /// Standard sections are named in upper case as LLVM's tools print them
+    /// (`CODE`, `DATA`, ...); custom sections keep their own name and hold only
+    /// their payload, after the name. `CODE` is the only executable section.
+    pub fn from_wasm(data: &[u8]) -> wasmparser::Result<Vec<Self>> {
+        use wasmparser::Payload;
+
+        let mut sections = vec![];
+        for payload in wasmparser::Parser::new(0).parse_all(data) {
+            let payload = payload?;
+            let Some((id, range)) = payload.as_section() else {
+                continue;
+            };
+            let (name, range, flags) = match &payload {
+                Payload::CustomSection(custom) => (
+                    custom.name().to_string(),
+                    custom.data_offset()..range.end,
+                    0,
+                ),
+                _ => {
+                    let flags = match payload {
+                        Payload::CodeSectionStart { .. } => SHF_ALLOC | SHF_EXECINSTR,
+                        Payload::DataSection(_) | Payload::GlobalSection(_) => {
+                            SHF_ALLOC | SHF_WRITE
+                        }
+                        _ => SHF_ALLOC,
+                    };
+                    (wasm_section_name(id).to_string(), range, flags)
+                }
+            };
+
+            sections.push(KSection {
+                name,
+                vma: range.start as u64,
+                size: range.len() as u64,
+                file_offset: range.start as u64,
+                flags: flags as u64,
+                raw_data: PlatformType::Wasm(data[range].to_vec()),
+            });
+        }
+        Ok(sections)
+    }
+
     pub fn from_goblin_ph<R: io::Seek + io::Read>(
         cursor: &mut R,
         elf: &Elf,
@@ -64,11 +297,18 @@ impl KSection {
                 continue;
             }
 
-            if ph.p_filesz == 0 || (ph.p_offset as usize + ph.p_filesz as usize) > buf_len {
+            if ph.p_filesz == 0
+                || ph
+                    .p_offset
+                    .checked_add(ph.p_filesz)
+                    .is_none_or(|end| end > buf_len as u64)
+            {
                 continue;
             }
 
-            let name = format!(".segment_{}", i);
+            let contains_entry =
+                (ph.p_vaddr..ph.p_vaddr.saturating_add(ph.p_memsz)).contains(&elf.entry);
+            let name = Self::segment_name(ph.p_flags, contains_entry, i, &sections);
             let mut raw = vec![0u8; ph.p_filesz as usize];
             cursor.seek(SeekFrom::Start(ph.p_offset))?;
             cursor.read_exact(&mut raw)?;
@@ -79,7 +319,7 @@ impl KSection {
                 vma: ph.p_vaddr,
                 size: ph.p_memsz, // Use p_memsz for virtual size
                 file_offset: ph.p_offset,
-                flags: ph.p_flags as u64,
+                flags: Self::segment_flags_to_shf(ph.p_flags),
                 raw_data: PlatformType::ELF(raw),
             };
             sections.push(x);
@@ -87,3 +327,59 @@ impl KSection {
         Ok(sections)
     }
 }
+
+/// LLVM's name for a standard WebAssembly section id
+fn wasm_section_name(id: u8) -> &'static str {
+    match id {
+        1 => "TYPE",
+        2 => "IMPORT",
+        3 => "FUNCTION",
+        4 => "TABLE",
+        5 => "MEMORY",
+        6 => "GLOBAL",
+        7 => "EXPORT",
+        8 => "START",
+        9 => "ELEM",
+        10 => "CODE",
+        11 => "DATA",
+        12 => "DATACOUNT",
+        13 => "TAG",
+        _ => "UNKNOWN",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BinaryAnalysis;
+
+    #[test]
+    fn malformed_elf_is_an_error() {
+        let elf = include_bytes!("../tests/fixtures/x86_32_symtab.elf");
+
+        // .data's sh_size (section header 2 at 0x128 + 2 * 40) pointing far past the file
+        let mut oversized = elf.to_vec();
+        oversized[0x18c..0x190].copy_from_slice(&0xffff_fff0u32.to_le_bytes());
+        assert!(BinaryAnalysis::from_bytes(oversized).is_err());
+
+        // The section headers sit at the very end, so every truncation loses some
+        for len in 0..elf.len() {
+            assert!(BinaryAnalysis::from_bytes(elf[..len].to_vec()).is_err());
+        }
+    }
+
+    #[test]
+    fn duplicate_section_names_keep_every_section() {
+        let mut elf = include_bytes!("../tests/fixtures/x86_32_symtab.elf").to_vec();
+        // Rename .data to .text by pointing its sh_name (header 2) at .text's name
+        elf[0x178..0x17c].copy_from_slice(&0x1bu32.to_le_bytes());
+        let analysis = BinaryAnalysis::from_bytes(elf).unwrap();
+
+        let texts: Vec<_> = analysis
+            .sections_named(".text")
+            .map(|s| (s.vma, s.raw_len()))
+            .collect();
+        assert_eq!(texts, [(0x8048054, 0x10), (0x8048064, 4)]);
+        assert_eq!(analysis.get_section(".text").unwrap().vma, 0x8048054);
+        assert_eq!(analysis.get_section_data(".text").unwrap().len(), 0x10);
+    }
+}
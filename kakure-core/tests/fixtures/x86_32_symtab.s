@@ -0,0 +1,27 @@
+# Source for x86_32_symtab.elf; rebuild with:
+#   as --32 -o x86_32_symtab.o x86_32_symtab.s && ld -m elf_i386 -N -o x86_32_symtab.elf x86_32_symtab.o
+# and the stripped build-id pair (x86_32_buildid.elf and its companion .debug) with:
+#   ld -m elf_i386 -N --build-id=sha1 -o full.elf x86_32_symtab.o
+#   objcopy --only-keep-debug full.elf x86_32_buildid.debug && objcopy --strip-all full.elf x86_32_buildid.elf
+    .text
+    .globl _start
+    .type _start, @function
+_start:
+    push %ebp
+    mov %esp, %ebp
+    call helper
+    pop %ebp
+    ret
+    .size _start, .-_start
+    .globl helper
+    .type helper, @function
+helper:
+    mov $1, %eax
+    ret
+    .size helper, .-helper
+    .data
+    .globl counter
+    .type counter, @object
+counter:
+    .long 0
+    .size counter, 4
@@ -0,0 +1,64 @@
+# Source for x86_64_eh_frame.elf; rebuild with:
+#   as --64 -o x86_64_eh_frame.o x86_64_eh_frame.s && ld -m elf_x86_64 -N -o x86_64_eh_frame.elf x86_64_eh_frame.o
+#
+# The unwind info is written by hand so one FDE can use an absolute
+# (DW_EH_PE_absptr) initial location and the other a pc-relative one.
+    .text
+    .globl _start
+_start:
+    push %rbp
+    mov %rsp, %rbp
+    pop %rbp
+    ret
+_start_end:
+helper:
+    mov $1, %eax
+    ret
+helper_end:
+
+    .section .eh_frame, "a", @progbits
+abs_cie:
+    .long abs_cie_end - abs_cie - 4
+    .long 0                     # CIE id
+    .byte 1                     # version
+    .asciz "zR"
+    .uleb128 1                  # code alignment
+    .sleb128 -8                 # data alignment
+    .byte 16                    # return address register (%rip)
+    .uleb128 1                  # augmentation data length
+    .byte 0x00                  # FDE pointers: DW_EH_PE_absptr
+    .byte 0x0c, 7, 8            # DW_CFA_def_cfa: %rsp+8
+    .byte 0x90, 1               # DW_CFA_offset: %rip at cfa-8
+    .balign 8
+abs_cie_end:
+    .long abs_fde_end - abs_fde
+abs_fde:
+    .long abs_fde - abs_cie     # CIE pointer
+    .quad _start
+    .quad _start_end - _start
+    .uleb128 0
+    .balign 8
+abs_fde_end:
+pcrel_cie:
+    .long pcrel_cie_end - pcrel_cie - 4
+    .long 0
+    .byte 1
+    .asciz "zR"
+    .uleb128 1
+    .sleb128 -8
+    .byte 16
+    .uleb128 1
+    .byte 0x1b                  # FDE pointers: DW_EH_PE_pcrel | DW_EH_PE_sdata4
+    .byte 0x0c, 7, 8
+    .byte 0x90, 1
+    .balign 8
+pcrel_cie_end:
+    .long pcrel_fde_end - pcrel_fde
+pcrel_fde:
+    .long pcrel_fde - pcrel_cie
+    .long helper - .
+    .long helper_end - helper
+    .uleb128 0
+    .balign 8
+pcrel_fde_end:
+    .long 0                     # terminator
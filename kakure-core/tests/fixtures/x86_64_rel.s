@@ -0,0 +1,21 @@
+# Source for x86_64_rel.o, a relocatable object whose sections all start at 0;
+# rebuild with:
+#   as --64 -o x86_64_rel.o x86_64_rel.s
+    .text
+    .fill 4, 1, 0xcc
+    .globl first
+    .type first, @function
+first:
+    mov $1, %eax
+    ret
+    .size first, .-first
+    .fill 6, 1, 0xcc
+
+    .section .text.second, "ax", @progbits
+    .fill 8, 1, 0x90
+    .globl second
+    .type second, @function
+second:
+    mov $2, %eax
+    ret
+    .size second, .-second